@@ -0,0 +1,531 @@
+//! Single-file submission bundler
+//!
+//! Reads a solution that uses `pral::...`, and prints it together with the minimal subset of this crate
+//! it depends on, as one self-contained source file.
+//!
+//! ```sh
+//! cargo run --bin pral-bundle -- main.rs > submit.rs
+//! cargo run --bin pral-bundle -- main.rs -o submit.rs
+//! ```
+//!
+//! - Only the files (leaf modules such as `data/seg.rs`) whose items are referenced are inlined.
+//! - References between library files (`crate::math::ModNum`, `super::seg::SegmentTree`) are followed transitively.
+//! - `#[test]` / `#[cfg(test)]` items and comments are stripped.
+//! - Every `pral::` path in the solution is rewritten to `crate::pral::`, so nested modules keep working.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Kind {
+    Ident,
+    Punct,
+    Literal,
+    Lifetime,
+    Comment,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Token {
+    kind: Kind,
+    start: usize,
+    end: usize,
+}
+
+/// A minimal Rust lexer. It only has to be precise about comments, string and char literals.
+fn tokenize(src: &str) -> Vec<Token> {
+    let s = src.as_bytes();
+    let n = s.len();
+    let is_ident = |c: u8| c == b'_' || c.is_ascii_alphanumeric();
+    let skip_string = |mut i: usize| {
+        i += 1;
+        while i < n && s[i] != b'"' {
+            i += if s[i] == b'\\' { 2 } else { 1 };
+        }
+        (i + 1).min(n)
+    };
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < n {
+        let c = s[i];
+        let start = i;
+        let kind = if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        } else if s[i..].starts_with(b"//") {
+            while i < n && s[i] != b'\n' {
+                i += 1;
+            }
+            Kind::Comment
+        } else if s[i..].starts_with(b"/*") {
+            let mut depth = 0;
+            while i < n {
+                if s[i..].starts_with(b"/*") {
+                    depth += 1;
+                    i += 2;
+                } else if s[i..].starts_with(b"*/") {
+                    depth -= 1;
+                    i += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    i += 1;
+                }
+            }
+            Kind::Comment
+        } else if c == b'"' {
+            i = skip_string(i);
+            Kind::Literal
+        } else if c == b'\'' {
+            let len = src[i + 1..].chars().next().map_or(0, char::len_utf8);
+            if s.get(i + 1) == Some(&b'\\') {
+                i += 3;
+                while i < n && s[i] != b'\'' {
+                    i += 1;
+                }
+                i += 1;
+                Kind::Literal
+            } else if s.get(i + 1 + len) == Some(&b'\'') {
+                i += len + 2;
+                Kind::Literal
+            } else {
+                i += 1;
+                while i < n && is_ident(s[i]) {
+                    i += 1;
+                }
+                Kind::Lifetime
+            }
+        } else if is_ident(c) {
+            while i < n && is_ident(s[i]) {
+                i += 1;
+            }
+            let word = &src[start..i];
+            if matches!(word, "r" | "br" | "cr") && i < n && (s[i] == b'"' || s[i] == b'#') {
+                let hashes = s[i..].iter().take_while(|&&c| c == b'#').count();
+                let close = format!("\"{}", "#".repeat(hashes));
+                i += hashes + 1;
+                i = src[i..].find(&close).map_or(n, |j| i + j + close.len());
+                Kind::Literal
+            } else if matches!(word, "b" | "c") && i < n && s[i] == b'"' {
+                i = skip_string(i);
+                Kind::Literal
+            } else if c.is_ascii_digit() {
+                Kind::Literal
+            } else {
+                Kind::Ident
+            }
+        } else {
+            i += if s[i..].starts_with(b"::") { 2 } else { src[i..].chars().next().map_or(1, char::len_utf8) };
+            Kind::Punct
+        };
+        tokens.push(Token { kind, start, end: i.min(n) });
+    }
+    tokens
+}
+
+/// Collects every path starting with `root` (use trees are expanded), e.g.
+/// `pral::{data::SegmentTree, math::*}` gives `["data", "SegmentTree"]` and `["math", "*"]`.
+fn collect_paths(src: &str, tokens: &[Token], root: &str) -> Vec<Vec<String>> {
+    fn tree(src: &str, t: &[Token], mut i: usize, prefix: &mut Vec<String>, out: &mut Vec<Vec<String>>) -> usize {
+        let text = |i: usize| t.get(i).map_or("", |t| &src[t.start..t.end]);
+        let depth = prefix.len();
+        loop {
+            match text(i) {
+                "{" => {
+                    i += 1;
+                    while text(i) != "}" && i < t.len() {
+                        i = tree(src, t, i, prefix, out);
+                        if text(i) == "," {
+                            i += 1;
+                        }
+                    }
+                    i += 1;
+                    break;
+                }
+                "*" | "self" => {
+                    prefix.push("*".to_string());
+                    out.push(prefix.clone());
+                    i += 1;
+                    break;
+                }
+                word if t.get(i).is_some_and(|t| t.kind == Kind::Ident) => {
+                    prefix.push(word.to_string());
+                    i += 1;
+                    if text(i) == "::" {
+                        i += 1;
+                    } else {
+                        out.push(prefix.clone());
+                        break;
+                    }
+                }
+                _ => {
+                    out.push(prefix.clone());
+                    break;
+                }
+            }
+        }
+        prefix.truncate(depth);
+        i
+    }
+    let t = tokens.iter().filter(|t| t.kind != Kind::Comment).copied().collect::<Vec<_>>();
+    let mut out = vec![];
+    for i in 0..t.len() {
+        let is_root = t[i].kind == Kind::Ident && &src[t[i].start..t[i].end] == root;
+        let qualified = i > 0 && &src[t[i - 1].start..t[i - 1].end] == "::";
+        if is_root && !qualified && t.get(i + 1).is_some_and(|t| &src[t.start..t.end] == "::") {
+            tree(src, &t, i + 2, &mut vec![], &mut out);
+        }
+    }
+    out
+}
+
+/// Replaces every unqualified `from::` path prefix with `to::`.
+fn rewrite_root(src: &str, from: &str, to: &str) -> String {
+    let tokens = tokenize(src).into_iter().filter(|t| t.kind != Kind::Comment).collect::<Vec<_>>();
+    let mut out = String::with_capacity(src.len());
+    let mut last = 0;
+    for (i, t) in tokens.iter().enumerate() {
+        let qualified = i > 0 && &src[tokens[i - 1].start..tokens[i - 1].end] == "::";
+        let followed = tokens.get(i + 1).is_some_and(|t| &src[t.start..t.end] == "::");
+        if t.kind == Kind::Ident && &src[t.start..t.end] == from && !qualified && followed {
+            out.push_str(&src[last..t.start]);
+            out.push_str(to);
+            last = t.end;
+        }
+    }
+    out.push_str(&src[last..]);
+    out
+}
+
+/// Removes comments and `#[test]` / `#[cfg(test)]` items, then squeezes blank lines.
+fn strip(src: &str) -> String {
+    let tokens = tokenize(src);
+    let text = |i: usize| tokens.get(i).map_or("", |t| &src[t.start..t.end]);
+    let mut removed = vec![];
+    let mut i = 0;
+    while i < tokens.len() {
+        if tokens[i].kind == Kind::Comment {
+            removed.push((tokens[i].start, tokens[i].end));
+            i += 1;
+            continue;
+        }
+        let is_test = text(i) == "#"
+            && text(i + 1) == "["
+            && (text(i + 2) == "test" && text(i + 3) == "]"
+                || text(i + 2) == "cfg" && text(i + 3) == "(" && text(i + 4) == "test" && text(i + 5) == ")");
+        if !is_test {
+            i += 1;
+            continue;
+        }
+        let start = tokens[i].start;
+        let mut depth = 0_i32;
+        let mut j = i;
+        while j < tokens.len() {
+            match text(j) {
+                "{" | "(" | "[" => depth += 1,
+                "}" | ")" | "]" => {
+                    depth -= 1;
+                    if depth == 0 && text(j) == "}" {
+                        break;
+                    }
+                }
+                ";" if depth == 0 => break,
+                _ => {}
+            }
+            j += 1;
+        }
+        let end = tokens.get(j).map_or(src.len(), |t| t.end);
+        removed.push((start, end));
+        i = j + 1;
+    }
+    let mut out = String::with_capacity(src.len());
+    let mut last = 0;
+    for (l, r) in removed {
+        out.push_str(&src[last..l]);
+        last = r;
+    }
+    out.push_str(&src[last..]);
+    let mut squeezed = String::with_capacity(out.len());
+    let mut blank = true;
+    for line in out.lines().map(str::trim_end) {
+        if line.is_empty() && blank {
+            continue;
+        }
+        blank = line.is_empty();
+        squeezed.push_str(line);
+        squeezed.push('\n');
+    }
+    squeezed.trim_end().to_string() + "\n"
+}
+
+/// The method names of each trait declared in `src`, so that glob-imported traits used only through their methods
+/// (`u64::gcd(a, b)`) are still detected.
+fn trait_methods(src: &str) -> BTreeMap<String, Vec<String>> {
+    let tokens = tokenize(src).into_iter().filter(|t| t.kind != Kind::Comment).collect::<Vec<_>>();
+    let text = |i: usize| tokens.get(i).map_or("", |t| &src[t.start..t.end]);
+    let mut traits = BTreeMap::new();
+    for i in (0..tokens.len()).filter(|&i| text(i) == "trait") {
+        let mut j = i + 2;
+        while j < tokens.len() && text(j) != "{" {
+            j += 1;
+        }
+        let mut depth = 0;
+        let mut methods = vec![];
+        while j < tokens.len() {
+            match text(j) {
+                "{" => depth += 1,
+                "}" => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                "fn" => methods.push(text(j + 1).to_string()),
+                _ => {}
+            }
+            j += 1;
+        }
+        traits.insert(text(i + 1).to_string(), methods);
+    }
+    traits
+}
+
+/// One `src/<module>/mod.rs`: which file declares which public item.
+#[derive(Default)]
+struct Module {
+    files: BTreeMap<String, Vec<String>>,
+    owner: BTreeMap<String, String>,
+}
+
+struct Library {
+    root: PathBuf,
+    modules: BTreeMap<String, Module>,
+    prelude: BTreeMap<String, String>,
+    methods: BTreeMap<String, Vec<String>>,
+}
+
+impl Library {
+    fn load(root: &Path) -> std::io::Result<Self> {
+        let statements = |path: PathBuf| -> std::io::Result<Vec<String>> {
+            let src = strip(&std::fs::read_to_string(path)?);
+            Ok(src.split(';').map(|s| s.split_whitespace().collect::<Vec<_>>().join(" ")).collect())
+        };
+        let declared = |s: &str, prefix: &str| {
+            let name = s.strip_prefix(prefix)?;
+            name.bytes().all(|c| c == b'_' || c.is_ascii_alphanumeric()).then(|| name.to_string())
+        };
+        // `pub use root::{..}` into `(root, paths)`
+        let parse_use = |s: &str| {
+            let s = &s[s.find("pub use ")? + "pub use ".len()..];
+            let root = s.split("::").next()?.to_string();
+            Some((root.clone(), collect_paths(s, &tokenize(s), &root)))
+        };
+        let mut modules = BTreeMap::new();
+        let mut prelude = BTreeMap::new();
+        let mut methods = BTreeMap::new();
+        for statement in statements(root.join("lib.rs"))? {
+            if let Some(name) = declared(&statement, "pub mod ") {
+                let mut module = Module::default();
+                for statement in statements(root.join(&name).join("mod.rs"))? {
+                    if let Some(file) = declared(&statement, "mod ") {
+                        module.files.entry(file).or_default();
+                    } else if let Some((file, paths)) = parse_use(&statement) {
+                        for item in paths.into_iter().filter_map(|p| p.last().cloned()) {
+                            module.owner.insert(item.clone(), file.clone());
+                            module.files.entry(file.clone()).or_default().push(item);
+                        }
+                    }
+                }
+                for file in module.files.keys() {
+                    let src = strip(&std::fs::read_to_string(root.join(&name).join(format!("{file}.rs")))?);
+                    methods.extend(trait_methods(&src));
+                }
+                modules.insert(name, module);
+            } else if let Some((_, paths)) = parse_use(&statement) {
+                for path in paths.into_iter().filter(|p| p.len() == 2) {
+                    prelude.insert(path[1].clone(), path[0].clone());
+                }
+            }
+        }
+        Ok(Self { root: root.to_path_buf(), modules, prelude, methods })
+    }
+    /// Whether `name` is used, directly or, for a trait, through one of its methods.
+    fn is_used(&self, name: &str, used: &BTreeSet<String>) -> bool {
+        used.contains(name) || self.methods.get(name).is_some_and(|m| m.iter().any(|f| used.contains(f)))
+    }
+    /// Resolves `[module, item, ..]` into `(module, file)`.
+    fn resolve(&self, path: &[String], used: &BTreeSet<String>, out: &mut BTreeSet<(String, String)>) {
+        let Some(first) = path.first() else {
+            return;
+        };
+        let item = path.get(1).map_or("*", String::as_str);
+        if first == "prelude" {
+            for (name, module) in self.prelude.iter() {
+                if name == item || item == "*" && self.is_used(name, used) {
+                    self.resolve(&[module.clone(), name.clone()], used, out);
+                }
+            }
+        } else if let Some(module) = self.modules.get(first) {
+            for (name, file) in module.owner.iter() {
+                if name == item || item == "*" && self.is_used(name, used) {
+                    out.insert((first.clone(), file.clone()));
+                }
+            }
+            if module.files.contains_key(item) {
+                let sub = path.get(2).map_or("*", String::as_str);
+                let names = &module.files[item];
+                if sub == "*" && names.iter().any(|s| self.is_used(s, used)) || names.iter().any(|s| s == sub) {
+                    out.insert((first.clone(), item.to_string()));
+                }
+            }
+        }
+    }
+    fn source(&self, module: &str, file: &str) -> std::io::Result<String> {
+        std::fs::read_to_string(self.root.join(module).join(format!("{file}.rs"))).map(|s| strip(&s))
+    }
+    fn bundle(&self, solution: &str) -> std::io::Result<String> {
+        let tokens = tokenize(solution);
+        let idents = |src: &str, tokens: &[Token]| {
+            tokens.iter().filter(|t| t.kind == Kind::Ident).map(|t| src[t.start..t.end].to_string()).collect()
+        };
+        let used = idents(solution, &tokens);
+        let paths = collect_paths(solution, &tokens, "pral");
+        if paths.is_empty() {
+            return Ok(solution.to_string());
+        }
+        // every module named in the solution is emitted, even if empty, so that its `use` still resolves
+        let referenced = paths.iter().filter_map(|p| p.first().cloned()).collect::<BTreeSet<_>>();
+        let mut files = BTreeSet::new();
+        for path in paths.iter() {
+            self.resolve(path, &used, &mut files);
+        }
+        let mut sources = BTreeMap::new();
+        let mut stack = files.iter().cloned().collect::<Vec<_>>();
+        while let Some((module, file)) = stack.pop() {
+            let src = self.source(&module, &file)?;
+            let tokens = tokenize(&src);
+            let used = idents(&src, &tokens);
+            let mut deps = BTreeSet::new();
+            for path in collect_paths(&src, &tokens, "crate") {
+                self.resolve(&path, &used, &mut deps);
+            }
+            for path in collect_paths(&src, &tokens, "super") {
+                let path = std::iter::once(module.clone()).chain(path).collect::<Vec<_>>();
+                self.resolve(&path, &used, &mut deps);
+            }
+            for dep in deps {
+                if files.insert(dep.clone()) {
+                    stack.push(dep);
+                }
+            }
+            sources.insert((module, file), rewrite_root(&src, "crate", "crate::pral"));
+        }
+        let mut out = rewrite_root(solution, "pral", "crate::pral");
+        out.push_str("\n#[allow(dead_code, unused_imports)]\nmod pral {\n");
+        let mut prelude = vec![];
+        for (name, module) in self.modules.iter() {
+            let included = module.files.keys().filter(|file| files.contains(&(name.clone(), file.to_string())));
+            let included = included.collect::<Vec<_>>();
+            if included.is_empty() && !referenced.contains(name) {
+                continue;
+            }
+            out.push_str(&format!("pub mod {name} {{\n"));
+            for file in included.iter() {
+                let items = &module.files[*file];
+                out.push_str(&format!("mod {file} {{\n{}}}\n", sources[&(name.clone(), file.to_string())]));
+                if !items.is_empty() {
+                    out.push_str(&format!("pub use {file}::{{{}}};\n", items.join(", ")));
+                }
+                let exported = items.iter().filter(|s| self.prelude.get(*s) == Some(name));
+                prelude.extend(exported.map(|s| format!("{name}::{s}")));
+            }
+            out.push_str("}\n");
+        }
+        out.push_str(&format!("pub mod prelude {{\npub use super::{{{}}};\n}}\n}}\n", prelude.join(", ")));
+        Ok(out)
+    }
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let mut input = None;
+    let mut output = None;
+    let mut root = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/src"));
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => output = args.next(),
+            "--src" => root = args.next().map(PathBuf::from).unwrap_or(root),
+            _ => input = Some(arg),
+        }
+    }
+    let Some(input) = input else {
+        eprintln!("usage: pral-bundle <solution.rs> [-o <output.rs>] [--src <pral/src>]");
+        std::process::exit(2);
+    };
+    let run = || -> std::io::Result<()> {
+        let solution = std::fs::read_to_string(&input)?;
+        let bundled = Library::load(&root)?.bundle(&solution)?;
+        match output {
+            Some(path) => std::fs::write(path, bundled),
+            None => {
+                use std::io::Write;
+                std::io::stdout().lock().write_all(bundled.as_bytes())
+            }
+        }
+    };
+    if let Err(err) = run() {
+        eprintln!("pral-bundle: {err}");
+        std::process::exit(1);
+    }
+}
+
+#[test]
+fn strip_tests_and_comments() {
+    let src = "/// doc\nfn f() -> char { '}' } // tail\n\n\n#[test]\nfn t() {\n    let s = \"}\";\n}\n#[cfg(test)]\nmod tests {\n    fn g<'a>(x: &'a str) {}\n}\nfn h() {}\n";
+    assert_eq!(strip(src), "fn f() -> char { '}' }\n\nfn h() {}\n");
+}
+
+#[test]
+fn use_trees() {
+    let src = "use pral::{data::{self, SegmentTree}, math::*};\nfn main() { pral::graph::Hld::new(vec![]); }";
+    let paths = collect_paths(src, &tokenize(src), "pral");
+    let paths = paths.iter().map(|p| p.join("::")).collect::<Vec<_>>();
+    assert_eq!(paths, ["data::*", "data::SegmentTree", "math::*", "graph::Hld::new"]);
+}
+
+#[test]
+fn bundle_only_used_modules() {
+    let lib = Library::load(Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src"))).unwrap();
+    let solution = "use pral::prelude::*;\nfn main() {\n    let mut seg = SegmentTree::new(4, 0, std::ops::Add::add);\n    seg.set(0, 1);\n}\n";
+    let bundled = lib.bundle(solution).unwrap();
+    assert!(bundled.starts_with("use crate::pral::prelude::*;"));
+    assert!(bundled.contains("mod seg {"));
+    assert!(bundled.contains("data::SegmentTree"));
+    assert!(!bundled.contains("DisjointSet"));
+    assert!(!bundled.contains("#[test]"));
+}
+
+#[test]
+fn bundle_compiles() {
+    let lib = Library::load(Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src"))).unwrap();
+    // `Gcd` is only used through its method, and nothing from `graph` is used at all
+    let solution = "use pral::graph::*;\nuse pral::prelude::*;\nfn main() {\n    let mut seg = SegmentTree::new(4, 0, std::ops::Add::add);\n    seg.set(1, u64::gcd(12, 18));\n    println!(\"{}\", seg.prod(0..4));\n}\n";
+    let bundled = lib.bundle(solution).unwrap();
+    assert!(bundled.contains("mod gcd {"));
+    assert!(bundled.contains("pub mod graph {"));
+    let dir = std::env::temp_dir().join(format!("pral-bundle-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("main.rs"), bundled).unwrap();
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let status = std::process::Command::new(rustc)
+        .args(["--edition", "2021", "-A", "warnings", "-o"])
+        .arg(dir.join("main"))
+        .arg(dir.join("main.rs"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let output = std::process::Command::new(dir.join("main")).output().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(output.stdout, b"6\n");
+}
@@ -0,0 +1,303 @@
+//! Algebraic structures
+//!
+//! The data structures are generic over these traits, so their types can be named in struct fields
+//! (e.g. `SegmentTree<Max<i64>>`) and one operation can be shared between several structures.
+//!
+//! Every structure only asks for what it needs:
+//!
+//! - [`super::SegmentTree`] needs a [`Monoid`]
+//! - [`super::BinaryIndexedTree`] needs a [`CommutativeMonoid`]
+//! - [`super::SparseTable`] needs an [`Idempotent`] monoid
+//! - [`super::DisjointSparseTable`] needs a [`Monoid`]
+//! - [`super::LazySeg`] needs a [`MapMonoid`]
+//! - [`super::WeightedDisjointSet`] needs a commutative [`Group`]
+//!
+//! The closure-based constructors (`new(size, e, f)`, ...) are kept, and wrap the closures in [`FnMonoid`] or [`FnMapMonoid`].
+//! Those of [`super::BinaryIndexedTree`] and [`super::SparseTable`] declare the closure commutative or idempotent.
+
+pub trait Monoid {
+    type S: Copy;
+    fn e(&self) -> Self::S;
+    fn op(&self, a: Self::S, b: Self::S) -> Self::S;
+}
+
+/// `op(a, b) == op(b, a)`
+pub trait CommutativeMonoid: Monoid {}
+
+/// `op(a, inv(a)) == op(inv(a), a) == e()`
+pub trait Group: Monoid {
+    fn inv(&self, a: Self::S) -> Self::S;
+}
+
+/// `op(a, a) == a`
+pub trait Idempotent: Monoid {}
+
+/// A monoid acted on by maps of type `F`.
+pub trait MapMonoid: Monoid {
+    type F: Copy;
+    fn id(&self) -> Self::F;
+    /// `x <- f(x)`
+    fn mapping(&self, x: &mut Self::S, f: Self::F);
+    /// `f <- g ∘ f`
+    fn composition(&self, f: &mut Self::F, g: Self::F);
+    /// The value of a leaf that has not been set, [`Monoid::e`] by default.
+    ///
+    /// Presets whose nodes carry a length give it length one, so that a range map reaches it.
+    fn init(&self) -> Self::S {
+        self.e()
+    }
+    /// Whether [`Self::mapping`] could not be applied to `x`, so it has to be recomputed from its children.
+    ///
+    /// Only segment tree beats needs this. Mapping a leaf must never fail.
+    fn fails(&self, _x: &Self::S) -> bool {
+        false
+    }
+}
+
+/// Adapter for an `(e, f)` closure pair.
+///
+/// A closure cannot tell its properties, so [`FnMonoid::new`] is only a [`Monoid`].
+/// The caller opts in with [`FnMonoid::commutative`] or [`FnMonoid::idempotent`], which are recorded in the type.
+#[derive(Clone, Copy)]
+pub struct FnMonoid<T, F, const COMMUTATIVE: bool = false, const IDEMPOTENT: bool = false> {
+    e: T,
+    f: F,
+}
+
+impl<T, F> FnMonoid<T, F>
+where
+    T: Copy,
+    F: Fn(T, T) -> T,
+{
+    pub fn new(e: T, f: F) -> Self {
+        Self { e, f }
+    }
+}
+
+impl<T, F> FnMonoid<T, F, true, false>
+where
+    T: Copy,
+    F: Fn(T, T) -> T,
+{
+    /// `f` must be commutative.
+    pub fn commutative(e: T, f: F) -> Self {
+        Self { e, f }
+    }
+}
+
+impl<T, F> FnMonoid<T, F, false, true>
+where
+    T: Copy,
+    F: Fn(T, T) -> T,
+{
+    /// `f` must be idempotent.
+    pub fn idempotent(e: T, f: F) -> Self {
+        Self { e, f }
+    }
+}
+
+impl<T, F, const C: bool, const I: bool> Monoid for FnMonoid<T, F, C, I>
+where
+    T: Copy,
+    F: Fn(T, T) -> T,
+{
+    type S = T;
+    fn e(&self) -> T {
+        self.e
+    }
+    fn op(&self, a: T, b: T) -> T {
+        (self.f)(a, b)
+    }
+}
+
+impl<T: Copy, F: Fn(T, T) -> T, const I: bool> CommutativeMonoid for FnMonoid<T, F, true, I> {}
+impl<T: Copy, F: Fn(T, T) -> T, const C: bool> Idempotent for FnMonoid<T, F, C, true> {}
+
+/// Adapter for the five arguments of [`super::LazySeg::new`].
+#[derive(Clone, Copy)]
+pub struct FnMapMonoid<T, U, F1, F2, F3> {
+    e: T,
+    off: U,
+    op: F1,
+    mapping: F2,
+    composition: F3,
+}
+
+impl<T, U, F1, F2, F3> FnMapMonoid<T, U, F1, F2, F3>
+where
+    T: Copy,
+    U: Copy,
+    F1: Fn(T, T) -> T,
+    F2: Fn(&mut T, U),
+    F3: Fn(&mut U, U),
+{
+    pub fn new(e: T, off: U, op: F1, mapping: F2, composition: F3) -> Self {
+        Self { e, off, op, mapping, composition }
+    }
+}
+
+impl<T, U, F1, F2, F3> Monoid for FnMapMonoid<T, U, F1, F2, F3>
+where
+    T: Copy,
+    F1: Fn(T, T) -> T,
+{
+    type S = T;
+    fn e(&self) -> T {
+        self.e
+    }
+    fn op(&self, a: T, b: T) -> T {
+        (self.op)(a, b)
+    }
+}
+
+impl<T, U, F1, F2, F3> MapMonoid for FnMapMonoid<T, U, F1, F2, F3>
+where
+    T: Copy,
+    U: Copy,
+    F1: Fn(T, T) -> T,
+    F2: Fn(&mut T, U),
+    F3: Fn(&mut U, U),
+{
+    type F = U;
+    fn id(&self) -> U {
+        self.off
+    }
+    fn mapping(&self, x: &mut T, f: U) {
+        (self.mapping)(x, f)
+    }
+    fn composition(&self, f: &mut U, g: U) {
+        (self.composition)(f, g)
+    }
+}
+
+/// Types with the least and the greatest value, used as the identities of [`Max`] and [`Min`].
+pub trait Bounded: Copy + Ord {
+    const MIN: Self;
+    const MAX: Self;
+}
+
+macro_rules! impl_bounded {
+    ($($t: ty),*) => {$(
+        impl Bounded for $t {
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+        }
+    )*};
+}
+
+impl_bounded!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Addition that wraps around instead of overflowing, and its inverse.
+///
+/// Integers are then a group modulo `2^k`, so prefix differences of unsigned counters come out right.
+pub trait WrappingAdd: Copy + Default {
+    fn wrapping_add(self, rhs: Self) -> Self;
+    fn wrapping_neg(self) -> Self;
+}
+
+macro_rules! impl_wrapping_add {
+    ($($t: ty),*) => {$(
+        impl WrappingAdd for $t {
+            fn wrapping_add(self, rhs: Self) -> Self {
+                <$t>::wrapping_add(self, rhs)
+            }
+            fn wrapping_neg(self) -> Self {
+                <$t>::wrapping_neg(self)
+            }
+        }
+    )*};
+}
+
+impl_wrapping_add!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Already modular, so there is nothing to wrap.
+impl<const M: u32> WrappingAdd for crate::math::ModNum<{ M }> {
+    fn wrapping_add(self, rhs: Self) -> Self {
+        self + rhs
+    }
+    fn wrapping_neg(self) -> Self {
+        self.neg()
+    }
+}
+
+/// `(T, +)` with `T::default()` as zero.
+#[derive(Clone, Copy, Default)]
+pub struct Additive<T>(std::marker::PhantomData<T>);
+
+impl<T: WrappingAdd> Monoid for Additive<T> {
+    type S = T;
+    fn e(&self) -> T {
+        T::default()
+    }
+    fn op(&self, a: T, b: T) -> T {
+        a.wrapping_add(b)
+    }
+}
+
+impl<T: WrappingAdd> CommutativeMonoid for Additive<T> {}
+
+impl<T: WrappingAdd> Group for Additive<T> {
+    fn inv(&self, a: T) -> T {
+        a.wrapping_neg()
+    }
+}
+
+/// `(T, ^)` with `T::default()` as zero. Every element is its own inverse.
+#[derive(Clone, Copy, Default)]
+pub struct Xor<T>(std::marker::PhantomData<T>);
+
+impl<T> Monoid for Xor<T>
+where
+    T: Copy + Default + std::ops::BitXor<Output = T>,
+{
+    type S = T;
+    fn e(&self) -> T {
+        T::default()
+    }
+    fn op(&self, a: T, b: T) -> T {
+        a ^ b
+    }
+}
+
+impl<T> CommutativeMonoid for Xor<T> where T: Copy + Default + std::ops::BitXor<Output = T> {}
+
+impl<T> Group for Xor<T>
+where
+    T: Copy + Default + std::ops::BitXor<Output = T>,
+{
+    fn inv(&self, a: T) -> T {
+        a
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct Min<T>(std::marker::PhantomData<T>);
+
+impl<T: Bounded> Monoid for Min<T> {
+    type S = T;
+    fn e(&self) -> T {
+        T::MAX
+    }
+    fn op(&self, a: T, b: T) -> T {
+        a.min(b)
+    }
+}
+
+impl<T: Bounded> CommutativeMonoid for Min<T> {}
+impl<T: Bounded> Idempotent for Min<T> {}
+
+#[derive(Clone, Copy, Default)]
+pub struct Max<T>(std::marker::PhantomData<T>);
+
+impl<T: Bounded> Monoid for Max<T> {
+    type S = T;
+    fn e(&self) -> T {
+        T::MIN
+    }
+    fn op(&self, a: T, b: T) -> T {
+        a.max(b)
+    }
+}
+
+impl<T: Bounded> CommutativeMonoid for Max<T> {}
+impl<T: Bounded> Idempotent for Max<T> {}
@@ -1,57 +1,71 @@
 //! Binary indexed tree
+//!
+//! - [`BinaryIndexedTree`]: point update, prefix product. With a [`Group`], also range product and point assignment.
+//! - [`RangeBinaryIndexedTree`]: range add, range sum over a commutative [`Group`], using two trees.
 
-struct BinaryIndexedTree<T, F> {
+use super::algebra::{CommutativeMonoid, FnMonoid, Group, Monoid};
+
+pub struct BinaryIndexedTree<M: CommutativeMonoid> {
     size: usize,
-    data: Box<[T]>,
-    e: T,
-    f: F,
+    data: Box<[M::S]>,
+    m: M,
 }
 
-impl<T, F> BinaryIndexedTree<T, F>
+/// `f` must be commutative.
+impl<T, F> BinaryIndexedTree<FnMonoid<T, F, true>>
 where
     T: Copy,
     F: Fn(T, T) -> T,
 {
-    fn new(size: usize, e: T, f: F) -> Self {
-        let data = vec![e; size].into_boxed_slice();
-        Self { size, data, e, f }
+    pub fn new(size: usize, e: T, f: F) -> Self {
+        Self::with_monoid(size, FnMonoid::commutative(e, f))
+    }
+    pub fn from(data: Vec<T>, e: T, f: F) -> Self {
+        Self::from_monoid(data, FnMonoid::commutative(e, f))
+    }
+}
+
+impl<M: CommutativeMonoid> BinaryIndexedTree<M> {
+    pub fn with_monoid(size: usize, m: M) -> Self {
+        let data = vec![m.e(); size].into_boxed_slice();
+        Self { size, data, m }
     }
-    fn from(data: Vec<T>, e: T, f: F) -> Self {
+    pub fn from_monoid(data: Vec<M::S>, m: M) -> Self {
         let size = data.len();
         let mut data = data.into_boxed_slice();
         for i in 1..size + 1 {
             let j = i + (i & i.wrapping_neg()) - 1;
             if j < size {
-                data[j] = f(data[i - 1], data[j]);
+                data[j] = m.op(data[i - 1], data[j]);
             }
         }
-        Self { size, data, e, f }
+        Self { size, data, m }
     }
-    fn update(&mut self, mut i: usize, v: T) {
+    pub fn update(&mut self, mut i: usize, v: M::S) {
         assert!(i <= self.size);
         i += 1;
         while i <= self.size {
-            self.data[i - 1] = (self.f)(v, self.data[i - 1]);
+            self.data[i - 1] = self.m.op(v, self.data[i - 1]);
             i += i & i.wrapping_neg();
         }
     }
-    fn prefix(&self, mut i: usize) -> T {
+    pub fn prefix(&self, mut i: usize) -> M::S {
         assert!(i <= self.size);
-        let mut ret = self.e;
+        let mut ret = self.m.e();
         while i != 0 {
-            ret = (self.f)(ret, self.data[i - 1]);
+            ret = self.m.op(ret, self.data[i - 1]);
             i &= i - 1;
         }
         ret
     }
-    fn partition_point<P: Fn(T) -> bool>(&self, pred: P) -> (usize, T) {
+    pub fn partition_point<P: Fn(M::S) -> bool>(&self, pred: P) -> (usize, M::S) {
         let mut i = 0;
-        let mut acc = self.e;
+        let mut acc = self.m.e();
         let mut len = 1 << self.size.ilog2();
         while len != 0 {
-            if i + len <= self.size && pred((self.f)(acc, self.data[i + len - 1])) {
+            if i + len <= self.size && pred(self.m.op(acc, self.data[i + len - 1])) {
                 i += len;
-                acc = (self.f)(acc, self.data[i - 1]);
+                acc = self.m.op(acc, self.data[i - 1]);
             }
             len >>= 1;
         }
@@ -59,6 +73,69 @@ where
     }
 }
 
+impl<M: CommutativeMonoid + Group> BinaryIndexedTree<M> {
+    pub fn range(&self, range: std::ops::Range<usize>) -> M::S {
+        assert!(range.start <= range.end);
+        self.m.op(self.prefix(range.end), self.m.inv(self.prefix(range.start)))
+    }
+    pub fn get(&self, i: usize) -> M::S {
+        self.range(i..i + 1)
+    }
+    pub fn set(&mut self, i: usize, v: M::S) {
+        let diff = self.m.op(v, self.m.inv(self.get(i)));
+        self.update(i, diff);
+    }
+}
+
+/// `prefix(i) = i * p(i) - q(i)`, where `p` and `q` are the prefix sums of two trees.
+/// Adding `v` to `l..r` adds `v` / `l * v` to `p` / `q` at `l`, and subtracts `v` / `r * v` at `r`.
+pub struct RangeBinaryIndexedTree<M: CommutativeMonoid + Group> {
+    p: BinaryIndexedTree<M>,
+    q: BinaryIndexedTree<M>,
+}
+
+impl<M: CommutativeMonoid + Group + Clone> RangeBinaryIndexedTree<M> {
+    pub fn with_monoid(size: usize, m: M) -> Self {
+        Self { p: BinaryIndexedTree::with_monoid(size + 1, m.clone()), q: BinaryIndexedTree::with_monoid(size + 1, m) }
+    }
+    pub fn from_monoid(data: Vec<M::S>, m: M) -> Self {
+        let size = data.len();
+        let q = data.into_iter().map(|x| m.inv(x)).chain(std::iter::once(m.e())).collect();
+        Self { p: BinaryIndexedTree::with_monoid(size + 1, m.clone()), q: BinaryIndexedTree::from_monoid(q, m) }
+    }
+    pub fn add(&mut self, range: std::ops::Range<usize>, v: M::S) {
+        assert!(range.start <= range.end && range.end < self.p.size);
+        let m = &self.p.m;
+        let (l, r) = (times(m, v, range.start), m.inv(times(m, v, range.end)));
+        let neg = m.inv(v);
+        self.p.update(range.start, v);
+        self.p.update(range.end, neg);
+        self.q.update(range.start, l);
+        self.q.update(range.end, r);
+    }
+    pub fn prefix(&self, i: usize) -> M::S {
+        let m = &self.p.m;
+        m.op(times(m, self.p.prefix(i), i), m.inv(self.q.prefix(i)))
+    }
+    pub fn range(&self, range: std::ops::Range<usize>) -> M::S {
+        assert!(range.start <= range.end);
+        self.p.m.op(self.prefix(range.end), self.p.m.inv(self.prefix(range.start)))
+    }
+}
+
+/// `x` combined `k` times.
+fn times<M: Monoid>(m: &M, mut x: M::S, mut k: usize) -> M::S {
+    let mut ret = m.e();
+    while k != 0 {
+        if k & 1 == 1 {
+            ret = m.op(ret, x);
+        }
+        x = m.op(x, x);
+        k >>= 1;
+    }
+    ret
+}
+
 #[test]
 fn prefix_sum() {
     let data = [2, -1, 3, 0, 3, -7, 10, 9];
@@ -103,3 +180,39 @@ fn partition_point() {
         assert!(j == data.len() || !pred(bit.prefix(j + 1)));
     }
 }
+
+#[test]
+fn group() {
+    use super::Additive;
+    let mut naive = vec![3_i64, -1, 4, 1, -5, 9, 2, 6];
+    let mut range = RangeBinaryIndexedTree::from_monoid(naive.clone(), Additive::default());
+    for (l, r, v) in [(0, 8, 2), (3, 5, -7), (6, 7, 10), (1, 1, 4), (2, 8, -1)] {
+        naive[l..r].iter_mut().for_each(|x| *x += v);
+        range.add(l..r, v);
+        for j in 0..=naive.len() {
+            for i in 0..=j {
+                assert_eq!(range.range(i..j), naive[i..j].iter().sum::<i64>());
+            }
+        }
+    }
+    let mut bit = BinaryIndexedTree::from_monoid(naive.clone(), Additive::default());
+    for (i, v) in [(0, 5), (7, -3), (4, 4), (4, 0)] {
+        bit.set(i, v);
+        naive[i] = v;
+        assert!((0..naive.len()).all(|i| bit.get(i) == naive[i]));
+        for j in 0..=naive.len() {
+            for i in 0..=j {
+                assert_eq!(bit.range(i..j), naive[i..j].iter().sum::<i64>());
+            }
+        }
+    }
+    // unsigned counters wrap around modulo 2^64, and the differences still come out right
+    let bit = BinaryIndexedTree::from_monoid(vec![1_u64, 2, 3, 4], Additive::default());
+    assert_eq!(bit.range(1..3), 5);
+    assert_eq!(bit.get(0), 1);
+    let mut range = RangeBinaryIndexedTree::from_monoid(vec![1_u64, 2, 3, 4], Additive::default());
+    range.add(1..4, 10);
+    range.add(0..2, 1 << 40);
+    assert_eq!(range.range(2..4), 27);
+    assert_eq!(range.range(0..2), 13 + (2 << 40));
+}
@@ -0,0 +1,226 @@
+//! 2D binary indexed tree
+//!
+//! Point update, prefix (rectangle) product on a grid.
+//!
+//! - [`BinaryIndexedTree2D`] is dense, O(hw) memory.
+//! - [`OfflineBinaryIndexedTree2D`] takes every point to be updated up front, so the coordinates can be huge.
+//!   Each node of the outer tree keeps its own compressed column coordinates, so the memory is O(n log n).
+//!
+//! # Time Complexity
+//!
+//! O(log h log w) per operation for the dense one, O(log² n) for the offline one.
+
+use super::algebra::{CommutativeMonoid, FnMonoid, Group};
+
+pub struct BinaryIndexedTree2D<M: CommutativeMonoid> {
+    h: usize,
+    w: usize,
+    data: Box<[M::S]>,
+    m: M,
+}
+
+/// `f` must be commutative.
+impl<T, F> BinaryIndexedTree2D<FnMonoid<T, F, true>>
+where
+    T: Copy,
+    F: Fn(T, T) -> T,
+{
+    pub fn new(h: usize, w: usize, e: T, f: F) -> Self {
+        Self::with_monoid(h, w, FnMonoid::commutative(e, f))
+    }
+}
+
+impl<M: CommutativeMonoid> BinaryIndexedTree2D<M> {
+    pub fn with_monoid(h: usize, w: usize, m: M) -> Self {
+        let data = vec![m.e(); h * w].into_boxed_slice();
+        Self { h, w, data, m }
+    }
+    pub fn update(&mut self, i: usize, j: usize, v: M::S) {
+        assert!(i < self.h && j < self.w);
+        let mut i = i + 1;
+        while i <= self.h {
+            let mut j = j + 1;
+            while j <= self.w {
+                let x = &mut self.data[(i - 1) * self.w + j - 1];
+                *x = self.m.op(v, *x);
+                j += j & j.wrapping_neg();
+            }
+            i += i & i.wrapping_neg();
+        }
+    }
+    /// The product of `[0, i) x [0, j)`.
+    pub fn prefix(&self, mut i: usize, j: usize) -> M::S {
+        assert!(i <= self.h && j <= self.w);
+        let mut ret = self.m.e();
+        while i != 0 {
+            let mut j = j;
+            while j != 0 {
+                ret = self.m.op(ret, self.data[(i - 1) * self.w + j - 1]);
+                j &= j - 1;
+            }
+            i &= i - 1;
+        }
+        ret
+    }
+}
+
+impl<M: CommutativeMonoid + Group> BinaryIndexedTree2D<M> {
+    /// The product of `rows x cols`.
+    pub fn rect(&self, rows: std::ops::Range<usize>, cols: std::ops::Range<usize>) -> M::S {
+        assert!(rows.start <= rows.end && cols.start <= cols.end);
+        rect(&self.m, |i, j| self.prefix(i, j), rows, cols)
+    }
+}
+
+/// Only the points given on construction can be updated.
+pub struct OfflineBinaryIndexedTree2D<M: CommutativeMonoid> {
+    xs: Box<[i64]>,
+    ys: Box<[Box<[i64]>]>,
+    data: Box<[Box<[M::S]>]>,
+    m: M,
+}
+
+/// `f` must be commutative.
+impl<T, F> OfflineBinaryIndexedTree2D<FnMonoid<T, F, true>>
+where
+    T: Copy,
+    F: Fn(T, T) -> T,
+{
+    pub fn new(points: &[(i64, i64)], e: T, f: F) -> Self {
+        Self::with_monoid(points, FnMonoid::commutative(e, f))
+    }
+}
+
+impl<M: CommutativeMonoid> OfflineBinaryIndexedTree2D<M> {
+    pub fn with_monoid(points: &[(i64, i64)], m: M) -> Self {
+        let mut xs = points.iter().map(|p| p.0).collect::<Vec<_>>();
+        xs.sort_unstable();
+        xs.dedup();
+        let mut ys = vec![vec![]; xs.len()];
+        for &(x, y) in points {
+            let mut i = xs.partition_point(|&v| v < x) + 1;
+            while i <= xs.len() {
+                ys[i - 1].push(y);
+                i += i & i.wrapping_neg();
+            }
+        }
+        let ys = ys
+            .into_iter()
+            .map(|mut v| {
+                v.sort_unstable();
+                v.dedup();
+                v.into_boxed_slice()
+            })
+            .collect::<Box<_>>();
+        let data = ys.iter().map(|v| vec![m.e(); v.len()].into_boxed_slice()).collect();
+        Self { xs: xs.into_boxed_slice(), ys, data, m }
+    }
+    /// Panics if `(x, y)` was not given on construction.
+    pub fn update(&mut self, x: i64, y: i64, v: M::S) {
+        let mut i = self.xs.binary_search(&x).expect("unregistered point") + 1;
+        while i <= self.xs.len() {
+            let (ys, data) = (&self.ys[i - 1], &mut self.data[i - 1]);
+            let mut j = ys.binary_search(&y).expect("unregistered point") + 1;
+            while j <= ys.len() {
+                data[j - 1] = self.m.op(v, data[j - 1]);
+                j += j & j.wrapping_neg();
+            }
+            i += i & i.wrapping_neg();
+        }
+    }
+    /// The product of the points with `px < x` and `py < y`.
+    pub fn prefix(&self, x: i64, y: i64) -> M::S {
+        let mut i = self.xs.partition_point(|&v| v < x);
+        let mut ret = self.m.e();
+        while i != 0 {
+            let mut j = self.ys[i - 1].partition_point(|&v| v < y);
+            while j != 0 {
+                ret = self.m.op(ret, self.data[i - 1][j - 1]);
+                j &= j - 1;
+            }
+            i &= i - 1;
+        }
+        ret
+    }
+}
+
+impl<M: CommutativeMonoid + Group> OfflineBinaryIndexedTree2D<M> {
+    /// The product of the points in `xs x ys`.
+    pub fn rect(&self, xs: std::ops::Range<i64>, ys: std::ops::Range<i64>) -> M::S {
+        if xs.start >= xs.end || ys.start >= ys.end {
+            return self.m.e();
+        }
+        rect(&self.m, |x, y| self.prefix(x, y), xs, ys)
+    }
+}
+
+fn rect<M: Group, T: Copy, P: Fn(T, T) -> M::S>(
+    m: &M,
+    prefix: P,
+    rows: std::ops::Range<T>,
+    cols: std::ops::Range<T>,
+) -> M::S {
+    let outer = m.op(prefix(rows.end, cols.end), prefix(rows.start, cols.start));
+    let inner = m.op(prefix(rows.start, cols.end), prefix(rows.end, cols.start));
+    m.op(outer, m.inv(inner))
+}
+
+#[test]
+fn dense() {
+    use super::Additive;
+    let (h, w) = (5, 7);
+    let mut naive = vec![vec![0_i64; w]; h];
+    let mut bit = BinaryIndexedTree2D::with_monoid(h, w, Additive::default());
+    for (i, j, v) in [(0, 0, 3), (4, 6, -2), (2, 3, 5), (1, 5, 7), (2, 3, 1), (3, 0, -4)] {
+        bit.update(i, j, v);
+        naive[i][j] += v;
+        for (r0, r1) in (0..=h).flat_map(|r1| (0..=r1).map(move |r0| (r0, r1))) {
+            for (c0, c1) in (0..=w).flat_map(|c1| (0..=c1).map(move |c0| (c0, c1))) {
+                let expected = naive[r0..r1].iter().map(|row| row[c0..c1].iter().sum::<i64>()).sum::<i64>();
+                assert_eq!(bit.rect(r0..r1, c0..c1), expected);
+            }
+        }
+    }
+    let mut max = BinaryIndexedTree2D::new(h, w, i32::MIN, std::cmp::max);
+    max.update(1, 2, 5);
+    max.update(3, 1, 8);
+    assert_eq!(max.prefix(2, 3), 5);
+    assert_eq!(max.prefix(4, 2), 8);
+    assert_eq!(max.prefix(4, 1), i32::MIN);
+}
+
+#[test]
+fn offline() {
+    use super::Additive;
+    let updates = [
+        (-1_000_000_000, 5, 3),
+        (7, 1_000_000_000, -2),
+        (7, -3, 4),
+        (0, 0, 10),
+        (-1_000_000_000, 5, 1),
+        (123_456_789, -987_654_321, 6),
+    ];
+    let points = updates.iter().map(|&(x, y, _)| (x, y)).collect::<Vec<_>>();
+    let mut bit = OfflineBinaryIndexedTree2D::with_monoid(&points, Additive::<i64>::default());
+    let coords =
+        [-1_000_000_001, -1_000_000_000, -987_654_321, -3, 0, 1, 5, 7, 8, 123_456_789, 1_000_000_000, i64::MAX];
+    let mut added = vec![];
+    for (x, y, v) in updates {
+        bit.update(x, y, v);
+        added.push((x, y, v));
+        for &x0 in &coords {
+            for &x1 in &coords {
+                for &y0 in &coords {
+                    for &y1 in &coords {
+                        let expected = added
+                            .iter()
+                            .filter(|p| x0 <= p.0 && p.0 < x1 && y0 <= p.1 && p.1 < y1)
+                            .map(|p| p.2)
+                            .sum::<i64>();
+                        assert_eq!(bit.rect(x0..x1, y0..y1), expected);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! - **Author** &emsp; palilo
 //! - **Source** &emsp; well known
-//! - **Update** &emsp; 2023-09-01
+//! - **Update** &emsp; 2026-10-18
 //!
 //! A DSU is a data structure that keeps track of a set of elements partitioned into a number of disjoint (non-overlapping) subsets.
 //!
@@ -19,34 +19,37 @@
 //!
 //! This near-constant time complexity is achieved by using two optimizations:
 //!
-//!  * **Path Compression**: During a find operation, this optimization flattens the structure of the tree by making every node on the find path point directly to the root.
+//!  * **Path Compression**: During a find operation, this optimization flattens the structure of the tree. Here it is _path halving_: every other node on the find path points to its grandparent, in a single iterative pass.
 //!  * **Union by Size/Rank**: During a union operation, this optimization attaches the smaller tree to the root of the larger tree. This keeps the tree structure relatively flat.
 
 /// A DSU with basic operations.
 ///
 /// # Note
 ///
-/// It is intended that `p` is public. It makes much easier to iterate the all roots or unite the nodes in different ways.
+/// It is intended that `p` is public. It makes much easier to unite the nodes in different ways.
+/// [`Self::leaders`], [`Self::groups`] and [`Self::labels`] cover the common loops over it.
 ///
-/// No [`assert!`], because the most important method [`Self::find`] is reculsively executed.
-struct DisjointSet {
-    p: Box<[i32]>,
+/// [`Self::find`] is iterative, so even a chain of length `n` does not overflow the stack.
+pub struct DisjointSet {
+    pub p: Box<[i32]>,
 }
 
 impl DisjointSet {
-    fn new(n: usize) -> Self {
+    pub fn new(n: usize) -> Self {
         Self { p: vec![-1; n].into() }
     }
-    fn find(&mut self, u: usize) -> usize {
-        if self.p[u] < 0 {
-            u
-        } else {
-            let root = self.find(self.p[u] as usize);
-            self.p[u] = root as i32;
-            root
+    pub fn find(&mut self, mut u: usize) -> usize {
+        while self.p[u] >= 0 {
+            let p = self.p[u] as usize;
+            if self.p[p] < 0 {
+                return p;
+            }
+            self.p[u] = self.p[p];
+            u = self.p[p] as usize;
         }
+        u
     }
-    fn unite(&mut self, u: usize, v: usize) -> bool {
+    pub fn unite(&mut self, u: usize, v: usize) -> bool {
         let mut u = self.find(u);
         let mut v = self.find(v);
         if u == v {
@@ -59,19 +62,44 @@ impl DisjointSet {
         self.p[v] = u as i32;
         true
     }
-    fn clear(&mut self) {
+    pub fn clear(&mut self) {
         self.p.fill(-1);
     }
-    fn same(&mut self, u: usize, v: usize) -> bool {
+    pub fn same(&mut self, u: usize, v: usize) -> bool {
         self.find(u) == self.find(v)
     }
-    fn size_of(&mut self, u: usize) -> usize {
+    pub fn size_of(&mut self, u: usize) -> usize {
         let root = self.find(u);
         (-self.p[root]) as usize
     }
-    fn num_components(&self) -> usize {
+    pub fn num_components(&self) -> usize {
         self.p.iter().filter(|x| x.is_negative()).count()
     }
+    /// The roots, in increasing order.
+    pub fn leaders(&self) -> Vec<usize> {
+        (0..self.p.len()).filter(|&u| self.p[u] < 0).collect()
+    }
+    /// Dense component ids in `0..num_components()`, numbered in order of the smallest member.
+    pub fn labels(&mut self) -> Vec<usize> {
+        let mut id = vec![usize::MAX; self.p.len()];
+        let mut cnt = 0;
+        (0..self.p.len())
+            .map(|u| {
+                let root = self.find(u);
+                if id[root] == usize::MAX {
+                    id[root] = cnt;
+                    cnt += 1;
+                }
+                id[root]
+            })
+            .collect()
+    }
+    /// The members of each component, in the order of [`Self::labels`].
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let mut groups = vec![vec![]; self.num_components()];
+        self.labels().into_iter().enumerate().for_each(|(u, id)| groups[id].push(u));
+        groups
+    }
 }
 
 #[test]
@@ -90,6 +118,22 @@ fn test() {
     assert_eq!(dsu.size_of(2), 3);
     assert_eq!(dsu.size_of(3), 1);
     assert_eq!(dsu.num_components(), 4);
+    assert_eq!(dsu.leaders().len(), 4);
+    assert_eq!(dsu.labels(), [0, 0, 0, 1, 2, 2, 3, 3]);
+    assert_eq!(dsu.groups(), [vec![0, 1, 2], vec![3], vec![4, 5], vec![6, 7]]);
     dsu.clear();
     assert_eq!(dsu.num_components(), 8);
 }
+
+#[test]
+fn long_chain() {
+    let n = 1_000_000;
+    let mut dsu = DisjointSet::new(n);
+    for u in 1..n {
+        dsu.p[u] = u as i32 - 1;
+    }
+    dsu.p[0] = -(n as i32);
+    assert_eq!(dsu.find(n - 1), 0);
+    assert_eq!(dsu.size_of(n / 2), n);
+    assert_eq!(dsu.leaders(), [0]);
+}
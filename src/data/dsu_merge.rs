@@ -0,0 +1,107 @@
+//! Disjoint Set Union with per-component data
+//!
+//! Each root owns a value of type `T`, such as a sum, a minimum, or a set of the members.
+//! When two components are united, the value of the smaller one is moved into the larger one
+//! by `merge(&mut large, small)`, so merging collections small-to-large costs O(n log n) moves in total.
+//!
+//! # Time Complexity
+//!
+//! O(α(n)) per operation, plus the cost of `merge`.
+
+pub struct MergeDisjointSet<T, F> {
+    p: Box<[i32]>,
+    data: Box<[Option<T>]>,
+    merge: F,
+}
+
+impl<T, F: FnMut(&mut T, T)> MergeDisjointSet<T, F> {
+    /// `data[u]` is the initial value of `{u}`.
+    pub fn new(data: Vec<T>, merge: F) -> Self {
+        Self { p: vec![-1; data.len()].into(), data: data.into_iter().map(Some).collect(), merge }
+    }
+    pub fn find(&mut self, mut u: usize) -> usize {
+        while self.p[u] >= 0 {
+            let p = self.p[u] as usize;
+            if self.p[p] >= 0 {
+                self.p[u] = self.p[p];
+            }
+            u = p;
+        }
+        u
+    }
+    pub fn unite(&mut self, u: usize, v: usize) -> bool {
+        let mut u = self.find(u);
+        let mut v = self.find(v);
+        if u == v {
+            return false;
+        }
+        if self.p[u] > self.p[v] {
+            std::mem::swap(&mut u, &mut v);
+        }
+        self.p[u] += self.p[v];
+        self.p[v] = u as i32;
+        let small = self.data[v].take().unwrap();
+        (self.merge)(self.data[u].as_mut().unwrap(), small);
+        true
+    }
+    pub fn same(&mut self, u: usize, v: usize) -> bool {
+        self.find(u) == self.find(v)
+    }
+    pub fn size_of(&mut self, u: usize) -> usize {
+        let root = self.find(u);
+        (-self.p[root]) as usize
+    }
+    pub fn num_components(&self) -> usize {
+        self.p.iter().filter(|x| x.is_negative()).count()
+    }
+    /// The value of the component of `u`.
+    pub fn get(&mut self, u: usize) -> &T {
+        let root = self.find(u);
+        self.data[root].as_ref().unwrap()
+    }
+    pub fn get_mut(&mut self, u: usize) -> &mut T {
+        let root = self.find(u);
+        self.data[root].as_mut().unwrap()
+    }
+    /// The members of each component, ordered by their smallest member.
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let mut id = vec![usize::MAX; self.p.len()];
+        let mut groups = Vec::<Vec<usize>>::new();
+        for u in 0..self.p.len() {
+            let root = self.find(u);
+            if id[root] == usize::MAX {
+                id[root] = groups.len();
+                groups.push(vec![]);
+            }
+            groups[id[root]].push(u);
+        }
+        groups
+    }
+}
+
+#[test]
+fn test() {
+    let mut dsu = MergeDisjointSet::new(vec![5_i64, -2, 7, 1, 3], |a: &mut i64, b| *a += b);
+    assert!(dsu.unite(0, 2));
+    assert!(dsu.unite(4, 1));
+    assert!(!dsu.unite(2, 0));
+    assert_eq!(*dsu.get(2), 12);
+    assert_eq!(*dsu.get(1), 1);
+    *dsu.get_mut(4) -= 10;
+    assert!(dsu.unite(1, 3));
+    assert_eq!(*dsu.get(3), -8);
+    assert_eq!(dsu.groups(), [vec![0, 2], vec![1, 3, 4]]);
+    assert_eq!(dsu.num_components(), 2);
+
+    use std::collections::BTreeMap;
+    let colors = [1, 2, 1, 3, 2, 2];
+    let maps = colors.iter().map(|&c| BTreeMap::from([(c, 1)])).collect();
+    let mut dsu = MergeDisjointSet::new(maps, |a: &mut BTreeMap<i32, usize>, b| {
+        b.into_iter().for_each(|(k, v)| *a.entry(k).or_default() += v)
+    });
+    for (u, v) in [(0, 1), (2, 3), (1, 3), (4, 5)] {
+        dsu.unite(u, v);
+    }
+    assert_eq!(*dsu.get(0), BTreeMap::from([(1, 2), (2, 1), (3, 1)]));
+    assert_eq!(*dsu.get(5), BTreeMap::from([(2, 2)]));
+}
@@ -0,0 +1,102 @@
+//! Parity Disjoint Set Union
+//!
+//! Online bipartiteness, and systems of "same colour" / "different colour" constraints.
+//!
+//! It is a [`super::WeightedDisjointSet`] over [`super::Xor`]`<bool>`, where the potential of a node is its colour
+//! relative to the root. Each root also remembers whether its component has an odd cycle.
+//!
+//! # Time Complexity
+//!
+//! O(α(n)) per operation.
+
+use super::algebra::Xor;
+use super::dsu_weighted::WeightedDisjointSet;
+
+pub struct ParityDisjointSet {
+    dsu: WeightedDisjointSet<Xor<bool>>,
+    odd: Box<[bool]>,
+}
+
+impl ParityDisjointSet {
+    pub fn new(n: usize) -> Self {
+        Self { dsu: WeightedDisjointSet::with_monoid(n, Xor::default()), odd: vec![false; n].into() }
+    }
+    pub fn find(&mut self, u: usize) -> usize {
+        self.dsu.find(u)
+    }
+    /// Adds the constraint that `u` and `v` have different colours if `different`, the same colour otherwise.
+    ///
+    /// Returns `false` if it contradicts the known constraints, i.e. it closes an odd cycle.
+    /// The component is then marked as not bipartite, and the constraint is dropped.
+    pub fn unite(&mut self, u: usize, v: usize, different: bool) -> bool {
+        let odd = self.odd[self.dsu.find(u)] | self.odd[self.dsu.find(v)];
+        let ok = self.dsu.unite(u, v, different).is_ok();
+        let root = self.dsu.find(u);
+        self.odd[root] = odd | !ok;
+        ok
+    }
+    /// Adds an edge, returning `false` if it creates an odd cycle.
+    pub fn add_edge(&mut self, u: usize, v: usize) -> bool {
+        self.unite(u, v, true)
+    }
+    /// Whether `u` and `v` have different colours, if they are in the same component.
+    pub fn parity(&mut self, u: usize, v: usize) -> Option<bool> {
+        self.dsu.diff(u, v)
+    }
+    /// Whether the component of `u` has no odd cycle.
+    pub fn is_bipartite(&mut self, u: usize) -> bool {
+        let root = self.dsu.find(u);
+        !self.odd[root]
+    }
+    pub fn same(&mut self, u: usize, v: usize) -> bool {
+        self.dsu.same(u, v)
+    }
+    pub fn size_of(&mut self, u: usize) -> usize {
+        self.dsu.size_of(u)
+    }
+    pub fn num_components(&self) -> usize {
+        self.dsu.num_components()
+    }
+    /// A 2-colouring of each component, where every root gets `false`.
+    /// The nodes of the components with an odd cycle get `None`.
+    pub fn coloring(&mut self) -> Vec<Option<bool>> {
+        (0..self.odd.len()).map(|u| self.is_bipartite(u).then(|| self.dsu.potential(u))).collect()
+    }
+}
+
+#[test]
+fn test() {
+    let mut dsu = ParityDisjointSet::new(6);
+    assert!(dsu.add_edge(0, 1));
+    assert!(dsu.add_edge(1, 2));
+    assert!(dsu.add_edge(3, 4));
+    assert!(dsu.add_edge(2, 3));
+    assert_eq!(dsu.parity(0, 2), Some(false));
+    assert_eq!(dsu.parity(0, 4), Some(false));
+    assert_eq!(dsu.parity(0, 5), None);
+    assert!(dsu.add_edge(4, 1));
+    let color = dsu.coloring();
+    assert!(color.iter().all(Option::is_some));
+    for (u, v) in [(0, 1), (1, 2), (3, 4), (2, 3), (4, 1)] {
+        assert_ne!(color[u], color[v]);
+    }
+    assert!(!dsu.add_edge(0, 2));
+    assert!(!dsu.is_bipartite(4));
+    assert!(dsu.is_bipartite(5));
+    assert_eq!(dsu.coloring(), [None, None, None, None, None, Some(false)]);
+    assert!(dsu.unite(5, 0, false));
+    assert!(!dsu.is_bipartite(5));
+    assert_eq!(dsu.num_components(), 1);
+}
+
+#[test]
+fn coloring_per_component() {
+    let mut dsu = ParityDisjointSet::new(6);
+    for (u, v) in [(0, 1), (1, 2), (3, 4), (4, 5)] {
+        assert!(dsu.add_edge(u, v));
+    }
+    assert!(!dsu.add_edge(2, 0));
+    let color = dsu.coloring();
+    assert_eq!(color[..3], [None; 3]);
+    assert!(color[3].is_some() && color[3] == color[5] && color[3] != color[4]);
+}
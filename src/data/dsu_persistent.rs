@@ -0,0 +1,118 @@
+//! Partially Persistent Disjoint Set Union
+//!
+//! A [`super::DisjointSet`] that can answer queries about the past.
+//! Time `t` is the state after the first `t` calls of `unite`.
+//!
+//! Without path compression, a link from a child to its parent never changes once made,
+//! so each link records the time it was made, and following only the links made by time `t` finds the root at `t`.
+//! Union by rank keeps the trees O(log n) deep, and the link times increase toward the root.
+//!
+//! # Time Complexity
+//!
+//! O(log n) per operation.
+
+pub struct PersistentDisjointSet {
+    p: Box<[u32]>,
+    rank: Box<[u8]>,
+    /// The time the link to the parent was made, `usize::MAX` for roots.
+    time: Box<[usize]>,
+    /// `(t, size)` whenever the size of a root changes.
+    sizes: Box<[Vec<(usize, usize)>]>,
+    now: usize,
+}
+
+impl PersistentDisjointSet {
+    pub fn new(n: usize) -> Self {
+        Self {
+            p: (0..n as u32).collect(),
+            rank: vec![0; n].into(),
+            time: vec![usize::MAX; n].into(),
+            sizes: vec![vec![(0, 1)]; n].into(),
+            now: 0,
+        }
+    }
+    /// Number of calls of [`Self::unite`] so far.
+    pub fn now(&self) -> usize {
+        self.now
+    }
+    pub fn find(&self, u: usize) -> usize {
+        self.find_at(u, self.now)
+    }
+    pub fn unite(&mut self, u: usize, v: usize) -> bool {
+        self.now += 1;
+        let mut u = self.find(u);
+        let mut v = self.find(v);
+        if u == v {
+            return false;
+        }
+        if self.rank[u] < self.rank[v] {
+            std::mem::swap(&mut u, &mut v);
+        }
+        if self.rank[u] == self.rank[v] {
+            self.rank[u] += 1;
+        }
+        let size = self.sizes[u].last().unwrap().1 + self.sizes[v].last().unwrap().1;
+        self.sizes[u].push((self.now, size));
+        self.p[v] = u as u32;
+        self.time[v] = self.now;
+        true
+    }
+    pub fn find_at(&self, mut u: usize, t: usize) -> usize {
+        while self.time[u] <= t {
+            u = self.p[u] as usize;
+        }
+        u
+    }
+    pub fn same_at(&self, u: usize, v: usize, t: usize) -> bool {
+        self.find_at(u, t) == self.find_at(v, t)
+    }
+    pub fn size_at(&self, u: usize, t: usize) -> usize {
+        let sizes = &self.sizes[self.find_at(u, t)];
+        sizes[sizes.partition_point(|&(s, _)| s <= t) - 1].1
+    }
+    /// The first time `t` such that `u` and `v` are connected at `t`.
+    pub fn first_connected_time(&self, mut u: usize, mut v: usize) -> Option<usize> {
+        let mut t = 0;
+        while u != v {
+            let next = if self.time[u] < self.time[v] { &mut u } else { &mut v };
+            t = self.time[*next];
+            if t == usize::MAX {
+                return None;
+            }
+            *next = self.p[*next] as usize;
+        }
+        Some(t)
+    }
+}
+
+#[test]
+fn test() {
+    use super::DisjointSet;
+    let n = 12;
+    let mut rng = crate::etc::Xrsr::with_seed(0x2545_f491_4f6c_dd1d);
+    let mut dsu = PersistentDisjointSet::new(n);
+    let mut naive = vec![DisjointSet::new(n)];
+    for t in 1..=20 {
+        let (u, v) = (rng.gen() % n, rng.gen() % n);
+        dsu.unite(u, v);
+        let mut next = DisjointSet::new(n);
+        next.p.copy_from_slice(&naive[t - 1].p);
+        next.unite(u, v);
+        naive.push(next);
+    }
+    assert_eq!(dsu.now(), 20);
+    for (t, naive) in naive.iter_mut().enumerate() {
+        for u in 0..n {
+            assert_eq!(dsu.size_at(u, t), naive.size_of(u));
+            for v in 0..n {
+                assert_eq!(dsu.same_at(u, v, t), naive.same(u, v));
+            }
+        }
+    }
+    for u in 0..n {
+        for v in 0..n {
+            let expected = (0..=20).find(|&t| naive[t].same(u, v));
+            assert_eq!(dsu.first_connected_time(u, v), expected);
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Rollback Disjoint Set Union
+//!
+//! A [`super::DisjointSet`] without path compression, so that every union can be undone.
+//! Union by size alone keeps the trees O(log n) deep.
+//!
+//! On top of it, [`dynamic_connectivity`] answers connectivity queries under edge insertions and deletions offline.
+//! Each edge is alive on an interval of time, which is split into O(log q) nodes of a segment tree over time.
+//! A DFS over the segment tree unites the edges of a node on entry, and rolls them back on exit.
+//!
+//! # Time Complexity
+//!
+//! - [`RollbackDisjointSet`]: O(log n) per operation.
+//! - [`dynamic_connectivity`]: O(q log q log n).
+
+pub struct RollbackDisjointSet {
+    p: Box<[i32]>,
+    /// `(v, p[v])` for each union, where the root `v` was attached to another root.
+    history: Vec<(u32, i32)>,
+}
+
+impl RollbackDisjointSet {
+    pub fn new(n: usize) -> Self {
+        Self { p: vec![-1; n].into(), history: vec![] }
+    }
+    pub fn find(&self, mut u: usize) -> usize {
+        while self.p[u] >= 0 {
+            u = self.p[u] as usize;
+        }
+        u
+    }
+    pub fn unite(&mut self, u: usize, v: usize) -> bool {
+        let mut u = self.find(u);
+        let mut v = self.find(v);
+        if u == v {
+            return false;
+        }
+        if self.p[u] > self.p[v] {
+            std::mem::swap(&mut u, &mut v);
+        }
+        self.history.push((v as u32, self.p[v]));
+        self.p[u] += self.p[v];
+        self.p[v] = u as i32;
+        true
+    }
+    pub fn same(&self, u: usize, v: usize) -> bool {
+        self.find(u) == self.find(v)
+    }
+    pub fn size_of(&self, u: usize) -> usize {
+        (-self.p[self.find(u)]) as usize
+    }
+    pub fn num_components(&self) -> usize {
+        self.p.len() - self.history.len()
+    }
+    /// A point to [`Self::rollback`] to.
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+    /// Undoes the unions made after `snapshot`.
+    pub fn rollback(&mut self, snapshot: usize) {
+        assert!(snapshot <= self.history.len());
+        for (v, size) in self.history.drain(snapshot..).rev() {
+            let u = self.p[v as usize] as usize;
+            self.p[u] -= size;
+            self.p[v as usize] = size;
+        }
+    }
+}
+
+/// An operation of [`dynamic_connectivity`].
+#[derive(Clone, Copy, Debug)]
+pub enum Event {
+    Add(usize, usize),
+    /// Removes one copy of an edge added before.
+    Remove(usize, usize),
+    /// Whether `u` and `v` are connected.
+    Query(usize, usize),
+}
+
+/// The answers to the `Event::Query`s, in order.
+pub fn dynamic_connectivity(n: usize, events: &[Event]) -> Vec<bool> {
+    let mut answers = vec![];
+    dynamic_connectivity_with(n, events, |t, dsu| {
+        if let Event::Query(u, v) = events[t] {
+            answers.push(dsu.same(u, v));
+        }
+    });
+    answers
+}
+
+/// Calls `f(t, dsu)` for each `Event::Query` at time `t`, where `dsu` holds exactly the edges alive at that time.
+///
+/// For the queries other than [`dynamic_connectivity`]'s, such as [`RollbackDisjointSet::num_components`].
+pub fn dynamic_connectivity_with<F: FnMut(usize, &RollbackDisjointSet)>(n: usize, events: &[Event], mut f: F) {
+    let q = events.len();
+    let size = q.next_power_of_two();
+    let mut edges = vec![vec![]; 2 * size];
+    let mut alive = std::collections::HashMap::<_, Vec<_>>::new();
+    let mut add = |l: usize, r: usize, e: (usize, usize)| {
+        let (mut l, mut r) = (l + size, r + size);
+        while l < r {
+            if l & 1 == 1 {
+                edges[l].push(e);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                edges[r].push(e);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+    };
+    for (t, &event) in events.iter().enumerate() {
+        match event {
+            Event::Add(u, v) => alive.entry((u.min(v), u.max(v))).or_default().push(t),
+            Event::Remove(u, v) => {
+                let e = (u.min(v), u.max(v));
+                let s = alive.get_mut(&e).and_then(|v| v.pop()).expect("removing an absent edge");
+                add(s, t, e);
+            }
+            Event::Query(..) => {}
+        }
+    }
+    for (e, starts) in alive {
+        starts.into_iter().for_each(|s| add(s, q, e));
+    }
+    let mut dsu = RollbackDisjointSet::new(n);
+    _dfs(1, size, &edges, events, &mut dsu, &mut f);
+}
+
+fn _dfs<F: FnMut(usize, &RollbackDisjointSet)>(
+    u: usize,
+    size: usize,
+    edges: &[Vec<(usize, usize)>],
+    events: &[Event],
+    dsu: &mut RollbackDisjointSet,
+    f: &mut F,
+) {
+    let snapshot = dsu.snapshot();
+    edges[u].iter().for_each(|&(a, b)| {
+        dsu.unite(a, b);
+    });
+    if u >= size {
+        if let Some(Event::Query(..)) = events.get(u - size) {
+            f(u - size, dsu);
+        }
+    } else {
+        _dfs(2 * u, size, edges, events, dsu, f);
+        _dfs(2 * u + 1, size, edges, events, dsu, f);
+    }
+    dsu.rollback(snapshot);
+}
+
+#[test]
+fn rollback() {
+    let mut dsu = RollbackDisjointSet::new(5);
+    dsu.unite(0, 1);
+    let s = dsu.snapshot();
+    assert!(dsu.unite(2, 3));
+    assert!(dsu.unite(1, 3));
+    assert!(!dsu.unite(0, 2));
+    assert_eq!(dsu.size_of(2), 4);
+    assert_eq!(dsu.num_components(), 2);
+    dsu.rollback(s);
+    assert!(dsu.same(0, 1) && !dsu.same(1, 2) && !dsu.same(2, 3));
+    assert_eq!(dsu.size_of(0), 2);
+    assert_eq!(dsu.num_components(), 4);
+    dsu.rollback(0);
+    assert_eq!(dsu.num_components(), 5);
+}
+
+#[test]
+fn offline() {
+    let n = 7;
+    let mut rng = crate::etc::Xrsr::with_seed(0x2545_f491_4f6c_dd1d);
+    let mut events = vec![];
+    let mut alive = vec![];
+    for _ in 0..300 {
+        match rng.gen() % 3 {
+            0 => {
+                let e = (rng.gen() % n, rng.gen() % n);
+                alive.push(e);
+                events.push(Event::Add(e.0, e.1));
+            }
+            1 if !alive.is_empty() => {
+                let (u, v) = alive.swap_remove(rng.gen() % alive.len());
+                events.push(if rng.gen() & 1 == 0 { Event::Remove(u, v) } else { Event::Remove(v, u) });
+            }
+            _ => events.push(Event::Query(rng.gen() % n, rng.gen() % n)),
+        }
+    }
+    let answers = dynamic_connectivity(n, &events);
+    let mut components = vec![];
+    dynamic_connectivity_with(n, &events, |t, dsu| components.push((t, dsu.num_components())));
+    assert_eq!(answers.len(), components.len());
+    for (answer, (t, components)) in answers.into_iter().zip(components) {
+        let mut naive = RollbackDisjointSet::new(n);
+        let mut alive = vec![];
+        for &event in &events[..t] {
+            match event {
+                Event::Add(u, v) => alive.push((u.min(v), u.max(v))),
+                Event::Remove(u, v) => {
+                    let i = alive.iter().position(|&e| e == (u.min(v), u.max(v))).unwrap();
+                    alive.remove(i);
+                }
+                Event::Query(..) => {}
+            }
+        }
+        alive.into_iter().for_each(|(u, v)| {
+            naive.unite(u, v);
+        });
+        assert_eq!(components, naive.num_components());
+        let Event::Query(u, v) = events[t] else { unreachable!() };
+        assert_eq!(answer, naive.same(u, v));
+    }
+}
@@ -0,0 +1,120 @@
+//! Weighted Disjoint Set Union _(Potential DSU)_
+//!
+//! A [`super::DisjointSet`] that also keeps a potential `x_u` for each element, known up to a constant per set.
+//! `unite(u, v, w)` adds the constraint `x_v - x_u = w`, and `diff(u, v)` answers `x_v - x_u` within a set.
+//!
+//! The potentials live in a commutative [`Group`]: [`super::Additive`] for integers or [`crate::math::ModNum`],
+//! [`super::Xor`] for parities and xor constraints.
+//!
+//! # Time Complexity
+//!
+//! O(α(n)) per operation, with union by size and path compression.
+
+use super::algebra::{CommutativeMonoid, Group};
+
+/// `w[u]` is `x_u - x_p`, where `p` is the parent of `u`.
+pub struct WeightedDisjointSet<M: CommutativeMonoid + Group> {
+    p: Box<[i32]>,
+    w: Box<[M::S]>,
+    m: M,
+}
+
+impl<M: CommutativeMonoid + Group> WeightedDisjointSet<M>
+where
+    M::S: PartialEq,
+{
+    pub fn with_monoid(n: usize, m: M) -> Self {
+        Self { p: vec![-1; n].into(), w: vec![m.e(); n].into(), m }
+    }
+    pub fn find(&mut self, u: usize) -> usize {
+        let mut root = u;
+        let mut pot = self.m.e();
+        while self.p[root] >= 0 {
+            pot = self.m.op(pot, self.w[root]);
+            root = self.p[root] as usize;
+        }
+        let mut u = u;
+        while self.p[u] >= 0 {
+            let (p, w) = (self.p[u] as usize, self.w[u]);
+            self.p[u] = root as i32;
+            self.w[u] = pot;
+            pot = self.m.op(pot, self.m.inv(w));
+            u = p;
+        }
+        root
+    }
+    /// `x_u - x_root`
+    pub fn potential(&mut self, u: usize) -> M::S {
+        let root = self.find(u);
+        if root == u {
+            self.m.e()
+        } else {
+            self.w[u]
+        }
+    }
+    /// Adds the constraint `x_v - x_u = w`.
+    ///
+    /// Returns whether two sets were merged, or `Err(diff(u, v))` if it contradicts the known constraints.
+    pub fn unite(&mut self, u: usize, v: usize, w: M::S) -> Result<bool, M::S> {
+        let (pu, pv) = (self.potential(u), self.potential(v));
+        let (mut u, mut v) = (self.find(u), self.find(v));
+        if u == v {
+            let diff = self.m.op(pv, self.m.inv(pu));
+            return if diff == w { Ok(false) } else { Err(diff) };
+        }
+        // x_rv - x_ru
+        let mut w = self.m.op(self.m.op(w, pu), self.m.inv(pv));
+        if self.p[u] > self.p[v] {
+            std::mem::swap(&mut u, &mut v);
+            w = self.m.inv(w);
+        }
+        self.p[u] += self.p[v];
+        self.p[v] = u as i32;
+        self.w[v] = w;
+        Ok(true)
+    }
+    /// `x_v - x_u`, if `u` and `v` are in the same set.
+    pub fn diff(&mut self, u: usize, v: usize) -> Option<M::S> {
+        let (pu, pv) = (self.potential(u), self.potential(v));
+        self.same(u, v).then(|| self.m.op(pv, self.m.inv(pu)))
+    }
+    pub fn same(&mut self, u: usize, v: usize) -> bool {
+        self.find(u) == self.find(v)
+    }
+    pub fn size_of(&mut self, u: usize) -> usize {
+        let root = self.find(u);
+        (-self.p[root]) as usize
+    }
+    pub fn num_components(&self) -> usize {
+        self.p.iter().filter(|x| x.is_negative()).count()
+    }
+}
+
+#[test]
+fn test() {
+    use super::{Additive, Xor};
+    let mut dsu = WeightedDisjointSet::with_monoid(6, Additive::<i64>::default());
+    assert_eq!(dsu.unite(0, 1, 3), Ok(true));
+    assert_eq!(dsu.unite(2, 1, -4), Ok(true));
+    assert_eq!(dsu.unite(3, 4, 10), Ok(true));
+    assert_eq!(dsu.unite(4, 2, 1), Ok(true));
+    assert_eq!(dsu.diff(0, 2), Some(7));
+    assert_eq!(dsu.diff(3, 0), Some(4));
+    assert_eq!(dsu.unite(3, 1, 7), Ok(false));
+    assert_eq!(dsu.unite(3, 1, 12), Err(7));
+    assert_eq!(dsu.diff(0, 5), None);
+    assert_eq!(dsu.size_of(2), 5);
+    assert_eq!(dsu.num_components(), 2);
+
+    let mut dsu = WeightedDisjointSet::with_monoid(4, Xor::<u64>::default());
+    assert_eq!(dsu.unite(0, 1, 0b101), Ok(true));
+    assert_eq!(dsu.unite(2, 1, 0b011), Ok(true));
+    assert_eq!(dsu.diff(0, 2), Some(0b110));
+    assert_eq!(dsu.unite(2, 0, 0b111), Err(0b110));
+
+    type M = crate::math::ModNum<998_244_353>;
+    let mut dsu = WeightedDisjointSet::with_monoid(3, Additive::<M>::default());
+    assert_eq!(dsu.unite(0, 1, M::new(998_244_352)), Ok(true));
+    assert_eq!(dsu.unite(1, 2, M::new(5)), Ok(true));
+    assert_eq!(dsu.diff(2, 0), Some(M::new(998_244_349)));
+}
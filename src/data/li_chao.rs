@@ -0,0 +1,294 @@
+//! Li Chao tree
+//!
+//! Insert lines `y = a x + b` (or segments of them), and query the minimum (or maximum) at a point.
+//!
+//! Each node keeps the line that is the best at its middle point. Another line can only be better on one side of it,
+//! so an insertion swaps the lines if needed and goes down to that side.
+//!
+//! - [`LiChaoTree`] is over a fixed set of query coordinates, known in advance.
+//! - [`DynamicLiChaoTree`] is over a range of `i64`, whose nodes are created on demand in an arena (`Vec`).
+//!
+//! The values are generic, so `i128` can be used when `a x + b` overflows `i64`. A max tree stores the negated lines.
+//!
+//! # Time Complexity
+//!
+//! O(log n) per line, O(log² n) per segment, O(log n) per query.
+
+use std::ops::{Add, Mul, Neg};
+
+#[derive(Clone, Copy)]
+struct Line<T> {
+    a: T,
+    b: T,
+}
+
+impl<T> Line<T>
+where
+    T: Copy + Ord + Add<Output = T> + Mul<Output = T> + Neg<Output = T> + From<i64>,
+{
+    fn new(a: T, b: T, max: bool) -> Self {
+        if max {
+            Self { a: -a, b: -b }
+        } else {
+            Self { a, b }
+        }
+    }
+    fn eval(&self, x: i64) -> T {
+        self.a * T::from(x) + self.b
+    }
+}
+
+pub struct LiChaoTree<T> {
+    n: usize,
+    /// Padded to a power of two with the last coordinate.
+    xs: Box<[i64]>,
+    lines: Box<[Option<Line<T>>]>,
+    max: bool,
+}
+
+impl<T> LiChaoTree<T>
+where
+    T: Copy + Ord + Add<Output = T> + Mul<Output = T> + Neg<Output = T> + From<i64>,
+{
+    /// Minimum queries at the coordinates `xs`.
+    pub fn min(xs: Vec<i64>) -> Self {
+        Self::_new(xs, false)
+    }
+    /// Maximum queries at the coordinates `xs`.
+    pub fn max(xs: Vec<i64>) -> Self {
+        Self::_new(xs, true)
+    }
+    pub fn add_line(&mut self, a: T, b: T) {
+        self._insert(1, Line::new(a, b, self.max));
+    }
+    /// Adds the segment of `y = a x + b` on `range`.
+    pub fn add_segment(&mut self, range: std::ops::Range<i64>, a: T, b: T) {
+        let line = Line::new(a, b, self.max);
+        let size = self.xs.len();
+        let mut l = self.xs[..self.n].partition_point(|&x| x < range.start) + size;
+        let mut r = self.xs[..self.n].partition_point(|&x| x < range.end) + size;
+        while l < r {
+            if l & 1 == 1 {
+                self._insert(l, line);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                self._insert(r, line);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+    }
+    /// The minimum (maximum) at `x`, which must be one of the coordinates. `None` if no line covers `x`.
+    pub fn query(&self, x: i64) -> Option<T> {
+        let i = self.xs[..self.n].binary_search(&x).expect("not a query coordinate");
+        let mut k = i + self.xs.len();
+        let mut res = None::<T>;
+        while k != 0 {
+            if let Some(line) = self.lines[k] {
+                let y = line.eval(x);
+                res = Some(res.map_or(y, |r| r.min(y)));
+            }
+            k >>= 1;
+        }
+        res.map(|y| if self.max { -y } else { y })
+    }
+    fn _new(mut xs: Vec<i64>, max: bool) -> Self {
+        xs.sort_unstable();
+        xs.dedup();
+        assert!(!xs.is_empty());
+        let n = xs.len();
+        let size = n.next_power_of_two();
+        xs.resize(size, xs[n - 1]);
+        Self { n, xs: xs.into(), lines: vec![None; size << 1].into(), max }
+    }
+    /// Inserts `line` into the subtree of node `k`.
+    fn _insert(&mut self, mut k: usize, mut line: Line<T>) {
+        let size = self.xs.len();
+        let d = size.trailing_zeros() - k.ilog2();
+        let (mut lo, mut hi) = ((k << d) - size, ((k + 1) << d) - size);
+        loop {
+            let Some(cur) = &mut self.lines[k] else {
+                self.lines[k] = Some(line);
+                return;
+            };
+            let mid = (lo + hi) / 2;
+            if line.eval(self.xs[mid]) < cur.eval(self.xs[mid]) {
+                std::mem::swap(cur, &mut line);
+            }
+            if hi - lo == 1 {
+                return;
+            }
+            let cur = *cur;
+            if line.eval(self.xs[lo]) < cur.eval(self.xs[lo]) {
+                (k, hi) = (k << 1, mid);
+            } else if line.eval(self.xs[hi - 1]) < cur.eval(self.xs[hi - 1]) {
+                (k, lo) = (k << 1 | 1, mid);
+            } else {
+                return;
+            }
+        }
+    }
+}
+
+struct Node<T> {
+    line: Option<Line<T>>,
+    ch: [u32; 2],
+}
+
+/// Nodes refer to their children by index, `0` meaning _absent_ (the root is never a child).
+pub struct DynamicLiChaoTree<T> {
+    lo: i64,
+    hi: i64,
+    nodes: Vec<Node<T>>,
+    max: bool,
+}
+
+impl<T> DynamicLiChaoTree<T>
+where
+    T: Copy + Ord + Add<Output = T> + Mul<Output = T> + Neg<Output = T> + From<i64>,
+{
+    /// Minimum queries on `range`.
+    pub fn min(range: std::ops::Range<i64>) -> Self {
+        Self::_new(range, false)
+    }
+    /// Maximum queries on `range`.
+    pub fn max(range: std::ops::Range<i64>) -> Self {
+        Self::_new(range, true)
+    }
+    pub fn add_line(&mut self, a: T, b: T) {
+        let line = Line::new(a, b, self.max);
+        self._insert(0, self.lo, self.hi, line);
+    }
+    /// Adds the segment of `y = a x + b` on `range`.
+    pub fn add_segment(&mut self, range: std::ops::Range<i64>, a: T, b: T) {
+        let line = Line::new(a, b, self.max);
+        let (l, r) = (range.start.max(self.lo), range.end.min(self.hi));
+        if l < r {
+            self._insert_segment(0, self.lo, self.hi, l, r, line);
+        }
+    }
+    /// The minimum (maximum) at `x`. `None` if no line covers `x`.
+    pub fn query(&self, x: i64) -> Option<T> {
+        assert!(self.lo <= x && x < self.hi);
+        let (mut u, mut l, mut r) = (0, self.lo, self.hi);
+        let mut res = None::<T>;
+        loop {
+            if let Some(line) = self.nodes[u].line {
+                let y = line.eval(x);
+                res = Some(res.map_or(y, |r| r.min(y)));
+            }
+            let mid = l + (r - l) / 2;
+            let b = (x >= mid) as usize;
+            (l, r) = if b == 0 { (l, mid) } else { (mid, r) };
+            u = self.nodes[u].ch[b] as usize;
+            if u == 0 {
+                break;
+            }
+        }
+        res.map(|y| if self.max { -y } else { y })
+    }
+    fn _new(range: std::ops::Range<i64>, max: bool) -> Self {
+        assert!(range.start < range.end && range.end.checked_sub(range.start).is_some());
+        Self { lo: range.start, hi: range.end, nodes: vec![Node { line: None, ch: [0; 2] }], max }
+    }
+    fn _child(&mut self, u: usize, b: usize) -> usize {
+        if self.nodes[u].ch[b] == 0 {
+            self.nodes[u].ch[b] = self.nodes.len() as u32;
+            self.nodes.push(Node { line: None, ch: [0; 2] });
+        }
+        self.nodes[u].ch[b] as usize
+    }
+    fn _insert(&mut self, mut u: usize, mut lo: i64, mut hi: i64, mut line: Line<T>) {
+        loop {
+            let Some(cur) = &mut self.nodes[u].line else {
+                self.nodes[u].line = Some(line);
+                return;
+            };
+            let mid = lo + (hi - lo) / 2;
+            if line.eval(mid) < cur.eval(mid) {
+                std::mem::swap(cur, &mut line);
+            }
+            if hi - lo == 1 {
+                return;
+            }
+            let cur = *cur;
+            if line.eval(lo) < cur.eval(lo) {
+                (u, hi) = (self._child(u, 0), mid);
+            } else if line.eval(hi - 1) < cur.eval(hi - 1) {
+                (u, lo) = (self._child(u, 1), mid);
+            } else {
+                return;
+            }
+        }
+    }
+    fn _insert_segment(&mut self, u: usize, lo: i64, hi: i64, l: i64, r: i64, line: Line<T>) {
+        if r <= lo || hi <= l {
+            return;
+        }
+        if l <= lo && hi <= r {
+            self._insert(u, lo, hi, line);
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let c0 = self._child(u, 0);
+        self._insert_segment(c0, lo, mid, l, r, line);
+        let c1 = self._child(u, 1);
+        self._insert_segment(c1, mid, hi, l, r, line);
+    }
+}
+
+#[test]
+fn test() {
+    let mut rng = crate::etc::Xrsr::with_seed(0x2545_f491_4f6c_dd1d);
+    let xs = (-20..30).collect::<Vec<i64>>();
+    let mut fixed_min = LiChaoTree::min(xs.iter().map(|x| x * 3).collect());
+    let mut fixed_max = LiChaoTree::max(xs.iter().map(|x| x * 3).collect());
+    let mut dynamic_min = DynamicLiChaoTree::min(-60..90);
+    let mut dynamic_max = DynamicLiChaoTree::max(-60..90);
+    let mut lines = vec![];
+    for _ in 0..60 {
+        let (a, b) = ((rng.gen() % 21) as i64 - 10, (rng.gen() % 201) as i64 - 100);
+        let (l, r) = if rng.gen() & 1 == 0 {
+            (i64::MIN, i64::MAX)
+        } else {
+            let l = (rng.gen() % 160) as i64 - 70;
+            (l, l + (rng.gen() % 80) as i64)
+        };
+        if l == i64::MIN {
+            fixed_min.add_line(a, b);
+            fixed_max.add_line(a, b);
+            dynamic_min.add_line(a, b);
+            dynamic_max.add_line(a, b);
+        } else {
+            fixed_min.add_segment(l..r, a, b);
+            fixed_max.add_segment(l..r, a, b);
+            dynamic_min.add_segment(l..r, a, b);
+            dynamic_max.add_segment(l..r, a, b);
+        }
+        lines.push((a, b, l, r));
+        for x in -60..90 {
+            let ys = lines.iter().filter(|&&(_, _, l, r)| l <= x && x < r).map(|&(a, b, _, _)| a * x + b);
+            let (min, max) = (ys.clone().min(), ys.max());
+            assert_eq!(dynamic_min.query(x), min);
+            assert_eq!(dynamic_max.query(x), max);
+            if x % 3 == 0 {
+                assert_eq!(fixed_min.query(x), min);
+                assert_eq!(fixed_max.query(x), max);
+            }
+        }
+    }
+}
+
+#[test]
+fn wide() {
+    let mut tree = DynamicLiChaoTree::<i128>::min(-1_000_000_000..1_000_000_001);
+    tree.add_line(1_000_000_000_000, 0);
+    tree.add_line(-1_000_000_000_000, 5);
+    tree.add_segment(0..10, 0, -7);
+    assert_eq!(tree.query(-1_000_000_000), Some(-1_000_000_000_000_000_000_000));
+    assert_eq!(tree.query(1_000_000_000), Some(-999_999_999_999_999_999_995));
+    assert_eq!(tree.query(3), Some(-2_999_999_999_995));
+    assert_eq!(tree.query(0), Some(-7));
+}
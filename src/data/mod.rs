@@ -1,7 +1,50 @@
 //! Data structures
 
+mod algebra;
 mod bit;
+mod bit_2d;
 mod dsu;
+mod dsu_merge;
+mod dsu_parity;
+mod dsu_persistent;
+mod dsu_rollback;
+mod dsu_weighted;
+mod li_chao;
+mod multiset;
+mod rmq;
 mod seg;
+mod seg_beats;
+mod seg_dual;
+mod seg_dynamic;
 mod seg_lazy;
+mod seg_lazy_preset;
+mod seg_persistent;
 mod sparse;
+mod wavelet;
+
+pub use algebra::{
+    Additive, Bounded, CommutativeMonoid, FnMapMonoid, FnMonoid, Group, Idempotent, MapMonoid, Max, Min, Monoid,
+    WrappingAdd, Xor,
+};
+pub use bit::{BinaryIndexedTree, RangeBinaryIndexedTree};
+pub use bit_2d::{BinaryIndexedTree2D, OfflineBinaryIndexedTree2D};
+pub use dsu::DisjointSet;
+pub use dsu_merge::MergeDisjointSet;
+pub use dsu_parity::ParityDisjointSet;
+pub use dsu_persistent::PersistentDisjointSet;
+pub use dsu_rollback::{dynamic_connectivity, dynamic_connectivity_with, Event, RollbackDisjointSet};
+pub use dsu_weighted::WeightedDisjointSet;
+pub use li_chao::{DynamicLiChaoTree, LiChaoTree};
+pub use multiset::OrderedMultiset;
+pub use rmq::Rmq;
+pub use seg::SegmentTree;
+pub use seg_beats::{BeatsMap, BeatsNode, RangeChminChmaxAddSum};
+pub use seg_dual::DualSegmentTree;
+pub use seg_dynamic::{DynamicLazySeg, DynamicSegmentTree};
+pub use seg_lazy::LazySeg;
+pub use seg_lazy_preset::{
+    Leaf, RangeAddMax, RangeAddMin, RangeAddSum, RangeAffineSum, RangeAssignMin, RangeAssignSum, RangeFlipCount,
+};
+pub use seg_persistent::{PersistentSegmentTree, Version};
+pub use sparse::{DisjointSparseTable, SparseTable};
+pub use wavelet::{BitVector, WaveletMatrix};
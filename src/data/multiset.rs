@@ -0,0 +1,134 @@
+//! Ordered multiset
+//!
+//! A multiset over a fixed universe of keys, counting each key in a [`BinaryIndexedTree`].
+//! Counts are `usize`, and a removal adds `usize::MAX`, which wraps around to `-1`.
+//! Order statistics descend the tree with [`BinaryIndexedTree::partition_point`].
+//!
+//! # Time Complexity
+//!
+//! O(log n) per operation, where n is the size of the universe.
+
+use super::algebra::Additive;
+use super::bit::BinaryIndexedTree;
+
+pub struct OrderedMultiset<T> {
+    keys: Box<[T]>,
+    bit: BinaryIndexedTree<Additive<usize>>,
+    len: usize,
+}
+
+impl OrderedMultiset<usize> {
+    /// The universe `0..n`.
+    pub fn bounded(n: usize) -> Self {
+        Self::new((0..n).collect())
+    }
+}
+
+impl<T: Copy + Ord> OrderedMultiset<T> {
+    /// Only the values in `universe` can be inserted.
+    pub fn new(mut universe: Vec<T>) -> Self {
+        universe.sort_unstable();
+        universe.dedup();
+        let bit = BinaryIndexedTree::with_monoid(universe.len(), Additive::default());
+        Self { keys: universe.into_boxed_slice(), bit, len: 0 }
+    }
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Panics if `x` is not in the universe.
+    pub fn insert(&mut self, x: T) {
+        let i = self.keys.binary_search(&x).expect("not in the universe");
+        self.bit.update(i, 1);
+        self.len += 1;
+    }
+    /// Removes one `x`, and returns whether it was present.
+    pub fn remove(&mut self, x: T) -> bool {
+        match self.keys.binary_search(&x) {
+            Ok(i) if self.bit.get(i) != 0 => {
+                self.bit.update(i, usize::MAX);
+                self.len -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+    pub fn count(&self, x: T) -> usize {
+        self.keys.binary_search(&x).map_or(0, |i| self.bit.get(i))
+    }
+    pub fn contains(&self, x: T) -> bool {
+        self.count(x) != 0
+    }
+    /// Number of elements less than `x`.
+    pub fn rank(&self, x: T) -> usize {
+        self.bit.prefix(self.keys.partition_point(|&k| k < x))
+    }
+    /// The `k`-th (0-indexed) smallest element.
+    pub fn kth(&self, k: usize) -> Option<T> {
+        if k >= self.len {
+            return None;
+        }
+        let (i, _) = self.bit.partition_point(|s| s <= k);
+        Some(self.keys[i])
+    }
+    /// The smallest element not less than `x`.
+    pub fn lower_bound(&self, x: T) -> Option<T> {
+        self.kth(self.rank(x))
+    }
+    /// The smallest element greater than `x`.
+    pub fn upper_bound(&self, x: T) -> Option<T> {
+        self.kth(self.bit.prefix(self.keys.partition_point(|&k| k <= x)))
+    }
+    /// The largest element less than `x`.
+    pub fn predecessor(&self, x: T) -> Option<T> {
+        self.rank(x).checked_sub(1).and_then(|k| self.kth(k))
+    }
+    /// The smallest element greater than `x`, same as [`Self::upper_bound`].
+    pub fn successor(&self, x: T) -> Option<T> {
+        self.upper_bound(x)
+    }
+    pub fn first(&self) -> Option<T> {
+        self.kth(0)
+    }
+    pub fn last(&self) -> Option<T> {
+        self.len.checked_sub(1).and_then(|k| self.kth(k))
+    }
+}
+
+#[test]
+fn test() {
+    let universe = vec![-7, 3, 10, 3, 25, 0, 99];
+    let mut set = OrderedMultiset::new(universe);
+    let mut naive = Vec::<i32>::new();
+    for (x, add) in [(3, true), (10, true), (3, true), (-7, true), (99, true), (3, false), (25, false), (0, true)] {
+        if add {
+            set.insert(x);
+            naive.push(x);
+            naive.sort();
+        } else {
+            let pos = naive.iter().position(|&v| v == x);
+            assert_eq!(set.remove(x), pos.is_some());
+            pos.map(|i| naive.remove(i));
+        }
+        assert_eq!(set.len(), naive.len());
+        for k in 0..=naive.len() {
+            assert_eq!(set.kth(k), naive.get(k).copied());
+        }
+        for x in -9..=101 {
+            assert_eq!(set.count(x), naive.iter().filter(|&&v| v == x).count());
+            assert_eq!(set.rank(x), naive.iter().filter(|&&v| v < x).count());
+            assert_eq!(set.lower_bound(x), naive.iter().find(|&&v| v >= x).copied());
+            assert_eq!(set.upper_bound(x), naive.iter().find(|&&v| v > x).copied());
+            assert_eq!(set.predecessor(x), naive.iter().rev().find(|&&v| v < x).copied());
+        }
+        assert_eq!(set.first(), naive.first().copied());
+        assert_eq!(set.last(), naive.last().copied());
+    }
+    let mut bounded = OrderedMultiset::bounded(5);
+    bounded.insert(4);
+    bounded.insert(1);
+    assert_eq!(bounded.kth(1), Some(4));
+    assert!(!bounded.remove(2));
+}
@@ -0,0 +1,166 @@
+//! Linear RMQ
+//!
+//! Range minimum (or maximum) queries returning the _position_, in O(n) memory.
+//!
+//! The array is split into blocks of 64. A [sparse table](super::SparseTable) over the winners of the blocks
+//! answers the whole blocks, and a bitmask per element answers the rest: the `j`-th bit of `mask[i]` is set iff
+//! `j` stays on the monotone stack after pushing `i`, so the lowest set bit at or after `l` is the winner of `l..=i`.
+//!
+//! For LCA, build it over the depths of an Euler tour (see the test).
+//!
+//! # Time Complexity
+//!
+//! O(n) build, O(1) query.
+
+const W: usize = u64::BITS as usize;
+
+/// `better(a, b)` tells whether `a` wins over `b`, where `b` lies to the left of `a`.
+/// A strict order (`<`) picks the leftmost of the tied elements, a non-strict one (`<=`) the rightmost.
+pub struct Rmq<T, F> {
+    data: Box<[T]>,
+    better: F,
+    masks: Box<[u64]>,
+    sparse: Box<[Box<[u32]>]>,
+}
+
+impl<T: Ord> Rmq<T, fn(&T, &T) -> bool> {
+    /// The leftmost minimum.
+    pub fn min(data: Vec<T>) -> Self {
+        Self::new(data, |a, b| a < b)
+    }
+    /// The leftmost maximum.
+    pub fn max(data: Vec<T>) -> Self {
+        Self::new(data, |a, b| a > b)
+    }
+}
+
+impl<T, F: Fn(&T, &T) -> bool> Rmq<T, F> {
+    pub fn new(data: Vec<T>, better: F) -> Self {
+        let n = data.len();
+        let mut masks = vec![0; n].into_boxed_slice();
+        for s in (0..n).step_by(W) {
+            let mut stack = 0_u64;
+            for i in s..n.min(s + W) {
+                while stack != 0 && better(&data[i], &data[s + top(stack)]) {
+                    stack ^= 1 << top(stack);
+                }
+                stack |= 1 << (i - s);
+                masks[i] = stack;
+            }
+        }
+        let mut rmq = Self { data: data.into(), better, masks, sparse: Box::new([]) };
+        let blocks = (0..n).step_by(W).map(|s| rmq._in_block(s, n.min(s + W) - 1) as u32).collect::<Vec<_>>();
+        let sparse = std::iter::successors(Some((blocks, 1)), |(prev, k)| {
+            let curr = prev.windows(k + 1).map(|w| rmq._pick(w[0] as usize, w[*k] as usize) as u32).collect::<Vec<_>>();
+            if curr.is_empty() {
+                None
+            } else {
+                Some((curr, k << 1))
+            }
+        })
+        .map(|x| x.0.into_boxed_slice())
+        .collect();
+        rmq.sparse = sparse;
+        rmq
+    }
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    /// The position of the winner in `range`.
+    pub fn arg(&self, range: std::ops::Range<usize>) -> usize {
+        assert!(range.start < range.end && range.end <= self.data.len());
+        let (l, r) = (range.start, range.end - 1);
+        let (bl, br) = (l / W, r / W);
+        if bl == br {
+            return self._in_block(l, r);
+        }
+        let mut res = self._in_block(l, bl * W + W - 1);
+        if bl + 1 < br {
+            let k = (br - bl - 1).ilog2() as usize;
+            let mid = self._pick(self.sparse[k][bl + 1] as usize, self.sparse[k][br - (1 << k)] as usize);
+            res = self._pick(res, mid);
+        }
+        self._pick(res, self._in_block(br * W, r))
+    }
+    /// The winner in `range`.
+    pub fn get(&self, range: std::ops::Range<usize>) -> &T {
+        &self.data[self.arg(range)]
+    }
+    /// `i <= j`
+    fn _pick(&self, i: usize, j: usize) -> usize {
+        if i != j && (self.better)(&self.data[j], &self.data[i]) {
+            j
+        } else {
+            i
+        }
+    }
+    /// The winner of `l..=r`, in the same block.
+    fn _in_block(&self, l: usize, r: usize) -> usize {
+        let s = l / W * W;
+        s + (self.masks[r] >> (l - s)).trailing_zeros() as usize + (l - s)
+    }
+}
+
+fn top(stack: u64) -> usize {
+    stack.ilog2() as usize
+}
+
+#[test]
+fn test() {
+    let mut rng = crate::etc::Xrsr::with_seed(0x2545_f491_4f6c_dd1d);
+    let a = (0..300).map(|_| rng.gen() % 10).collect::<Vec<_>>();
+    let leftmost_min = Rmq::min(a.clone());
+    let rightmost_max = Rmq::new(a.clone(), |a, b| a >= b);
+    for _ in 0..5000 {
+        let l = rng.gen() % a.len();
+        let r = l + 1 + rng.gen() % (a.len() - l);
+        let min = *a[l..r].iter().min().unwrap();
+        let max = *a[l..r].iter().max().unwrap();
+        assert_eq!(leftmost_min.arg(l..r), (l..r).find(|&i| a[i] == min).unwrap());
+        assert_eq!(rightmost_max.arg(l..r), (l..r).rfind(|&i| a[i] == max).unwrap());
+        assert_eq!(*leftmost_min.get(l..r), min);
+    }
+}
+
+#[test]
+fn lca() {
+    use crate::graph::Hld;
+    let n = 200;
+    let mut rng = crate::etc::Xrsr::with_seed(0x2545_f491_4f6c_dd1d);
+    let mut adj = vec![vec![]; n];
+    let mut children = vec![vec![]; n];
+    for v in 1..n {
+        let p = rng.gen() % v;
+        adj[p].push(v);
+        adj[v].push(p);
+        children[p].push(v);
+    }
+    // Euler tour of `(depth, vertex)`
+    let (mut tour, mut first, mut depth, mut next) = (vec![(0, 0)], vec![0; n], vec![0; n], vec![0; n]);
+    let mut stack = vec![0];
+    while let Some(&u) = stack.last() {
+        if let Some(&v) = children[u].get(next[u]) {
+            next[u] += 1;
+            depth[v] = depth[u] + 1;
+            first[v] = tour.len();
+            tour.push((depth[v], v));
+            stack.push(v);
+        } else {
+            stack.pop();
+            if let Some(&p) = stack.last() {
+                tour.push((depth[p], p));
+            }
+        }
+    }
+    let rmq = Rmq::min(tour);
+    let hld = Hld::new(adj);
+    for u in 0..n {
+        for v in 0..n {
+            let (l, r) = (first[u].min(first[v]), first[u].max(first[v]));
+            assert_eq!(rmq.get(l..r + 1).1, hld.get_lca(u, v));
+        }
+    }
+}
@@ -1,38 +1,47 @@
 //! Segment data
 
-struct SegmentTree<T, F> {
+use super::algebra::{FnMonoid, Monoid};
+
+pub struct SegmentTree<M: Monoid> {
+    /// The requested size, before padding to `size`.
+    n: usize,
     size: usize,
-    data: Box<[T]>,
-    e: T,
-    f: F,
+    data: Box<[M::S]>,
+    m: M,
 }
 
-impl<T, F> std::ops::Index<usize> for SegmentTree<T, F> {
-    type Output = T;
-    fn index(&self, i: usize) -> &T {
+impl<M: Monoid> std::ops::Index<usize> for SegmentTree<M> {
+    type Output = M::S;
+    fn index(&self, i: usize) -> &M::S {
         &self.data[i + self.size]
     }
 }
-impl<T, F> std::ops::IndexMut<usize> for SegmentTree<T, F> {
-    fn index_mut(&mut self, i: usize) -> &mut T {
+impl<M: Monoid> std::ops::IndexMut<usize> for SegmentTree<M> {
+    fn index_mut(&mut self, i: usize) -> &mut M::S {
         &mut self.data[i + self.size]
     }
 }
 
-impl<T, F> SegmentTree<T, F>
+impl<T, F> SegmentTree<FnMonoid<T, F>>
 where
     T: Copy,
     F: Fn(T, T) -> T,
 {
-    fn new(size: usize, e: T, f: F) -> Self {
-        let size = size.next_power_of_two();
-        let data = vec![e; size << 1].into_boxed_slice();
-        Self { size, data, e, f }
+    pub fn new(size: usize, e: T, f: F) -> Self {
+        Self::with_monoid(size, FnMonoid::new(e, f))
+    }
+}
+
+impl<M: Monoid> SegmentTree<M> {
+    pub fn with_monoid(n: usize, m: M) -> Self {
+        let size = n.next_power_of_two();
+        let data = vec![m.e(); size << 1].into_boxed_slice();
+        Self { n, size, data, m }
     }
-    fn build(&mut self) {
+    pub fn build(&mut self) {
         (1..self.size).rev().for_each(|i| self._pull(i));
     }
-    fn set(&mut self, mut i: usize, x: T) {
+    pub fn set(&mut self, mut i: usize, x: M::S) {
         assert!(i < self.size);
         i += self.size;
         self.data[i] = x;
@@ -41,31 +50,126 @@ where
             self._pull(i);
         }
     }
-    fn prod(&self, range: std::ops::Range<usize>) -> T {
+    pub fn prod(&self, range: std::ops::Range<usize>) -> M::S {
         assert!(range.start <= range.end && range.end <= self.size);
         let mut l = self.size + range.start;
         let mut r = self.size + range.end;
-        let mut prod_l = self.e;
-        let mut prod_r = self.e;
+        let mut prod_l = self.m.e();
+        let mut prod_r = self.m.e();
         while l != r {
             if l & 1 == 1 {
-                prod_l = (self.f)(prod_l, self.data[l]);
+                prod_l = self.m.op(prod_l, self.data[l]);
                 l += 1;
             }
             if r & 1 == 1 {
                 r -= 1;
-                prod_r = (self.f)(self.data[r], prod_r);
+                prod_r = self.m.op(self.data[r], prod_r);
             }
             l >>= 1;
             r >>= 1;
         }
-        (self.f)(prod_l, prod_r)
+        self.m.op(prod_l, prod_r)
+    }
+    /// Returns the largest `r <= n` such that `pred(prod(l..r))` holds,
+    /// assuming `pred` is monotone and `pred(e)` is true.
+    pub fn max_right<P: Fn(M::S) -> bool>(&self, l: usize, pred: P) -> usize {
+        assert!(l <= self.n && pred(self.m.e()));
+        if l == self.n {
+            return self.n;
+        }
+        let mut l = l + self.size;
+        let mut acc = self.m.e();
+        loop {
+            l >>= l.trailing_zeros();
+            if !pred(self.m.op(acc, self.data[l])) {
+                while l < self.size {
+                    l <<= 1;
+                    let next = self.m.op(acc, self.data[l]);
+                    if pred(next) {
+                        acc = next;
+                        l += 1;
+                    }
+                }
+                return (l - self.size).min(self.n);
+            }
+            acc = self.m.op(acc, self.data[l]);
+            l += 1;
+            if l.is_power_of_two() {
+                return self.n;
+            }
+        }
+    }
+    /// Returns the smallest `l` such that `pred(prod(l..r))` holds, assuming `pred` is monotone and `pred(e)` is true.
+    pub fn min_left<P: Fn(M::S) -> bool>(&self, r: usize, pred: P) -> usize {
+        assert!(r <= self.n && pred(self.m.e()));
+        if r == 0 {
+            return 0;
+        }
+        let mut r = r + self.size;
+        let mut acc = self.m.e();
+        loop {
+            r -= 1;
+            while r > 1 && r & 1 == 1 {
+                r >>= 1;
+            }
+            if !pred(self.m.op(self.data[r], acc)) {
+                while r < self.size {
+                    r = r << 1 | 1;
+                    let next = self.m.op(self.data[r], acc);
+                    if pred(next) {
+                        acc = next;
+                        r -= 1;
+                    }
+                }
+                return r + 1 - self.size;
+            }
+            acc = self.m.op(self.data[r], acc);
+            if r.is_power_of_two() {
+                return 0;
+            }
+        }
     }
     #[inline]
     fn _pull(&mut self, i: usize) {
-        self.data[i] = (self.f)(self.data[i << 1], self.data[i << 1 | 1]);
+        self.data[i] = self.m.op(self.data[i << 1], self.data[i << 1 | 1]);
     }
 }
 
 #[test]
-fn test() {}
+fn test() {
+    use super::algebra::Max;
+    let a = [3, -1, 4, 1, -5, 9, 2, -6];
+    let mut seg: SegmentTree<Max<i32>> = SegmentTree::with_monoid(a.len(), Max::default());
+    for (i, &x) in a.iter().enumerate() {
+        seg[i] = x;
+    }
+    seg.build();
+    for j in 0..=a.len() {
+        for i in 0..=j {
+            assert_eq!(a[i..j].iter().copied().max().unwrap_or(i32::MIN), seg.prod(i..j));
+        }
+    }
+}
+
+#[test]
+fn binary_search() {
+    let a = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+    let mut seg = SegmentTree::new(a.len(), 0, std::ops::Add::add);
+    for (i, &x) in a.iter().enumerate() {
+        seg[i] = x;
+    }
+    seg.build();
+    for x in 0..=a.iter().sum::<i32>() + 1 {
+        let pred = |s| s <= x;
+        for l in 0..=a.len() {
+            let r = seg.max_right(l, pred);
+            let naive = (l..=a.len()).rev().find(|&r| pred(a[l..r].iter().sum())).unwrap();
+            assert_eq!(r, naive);
+        }
+        for r in 0..=a.len() {
+            let l = seg.min_left(r, pred);
+            let naive = (0..=r).find(|&l| pred(a[l..r].iter().sum())).unwrap();
+            assert_eq!(l, naive);
+        }
+    }
+}
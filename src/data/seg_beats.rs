@@ -0,0 +1,190 @@
+//! Segment Tree Beats
+//!
+//! - **Author** &emsp; Ruyi Ji
+//! - **Source** &emsp; [Codeforces blog](https://codeforces.com/blog/entry/57319)
+//! - **Update** &emsp; 2026-10-18
+//!
+//! Range chmin, range chmax, range add, and range sum / min / max, on top of [`super::LazySeg`].
+//!
+//! A node keeps its minimum, maximum, and the strict second minimum / maximum.
+//! If a map only changes the extreme values of a node, it is applied in O(1), otherwise the node [fails](MapMonoid::fails)
+//! and [`super::LazySeg`] recomputes it from its children.
+//!
+//! # Time Complexity
+//!
+//! Amortized O(log² n) per operation.
+
+use super::algebra::{MapMonoid, Monoid};
+
+#[derive(Clone, Copy, Debug)]
+pub struct BeatsNode {
+    pub min: i64,
+    pub max: i64,
+    pub sum: i64,
+    min2: i64,
+    max2: i64,
+    cnt_min: i64,
+    cnt_max: i64,
+    len: i64,
+    fail: bool,
+}
+
+impl BeatsNode {
+    const EMPTY: Self = Self::uniform(i64::MAX, 0);
+    pub const fn new(x: i64) -> Self {
+        Self::uniform(x, 1)
+    }
+    const fn uniform(x: i64, len: i64) -> Self {
+        Self {
+            min: x,
+            max: if len == 0 { i64::MIN } else { x },
+            sum: x * len,
+            min2: i64::MAX,
+            max2: i64::MIN,
+            cnt_min: len,
+            cnt_max: len,
+            len,
+            fail: false,
+        }
+    }
+}
+
+/// `x <- min(max(x, lb), ub) + bias`
+#[derive(Clone, Copy, Debug)]
+pub struct BeatsMap {
+    lb: i64,
+    ub: i64,
+    bias: i64,
+}
+
+impl BeatsMap {
+    pub const fn chmin(x: i64) -> Self {
+        Self { lb: i64::MIN, ub: x, bias: 0 }
+    }
+    pub const fn chmax(x: i64) -> Self {
+        Self { lb: x, ub: i64::MAX, bias: 0 }
+    }
+    pub const fn add(x: i64) -> Self {
+        Self { lb: i64::MIN, ub: i64::MAX, bias: x }
+    }
+}
+
+/// Range chmin / chmax / add, range sum / min / max.
+///
+/// Initialize each leaf with [`BeatsNode::new`].
+#[derive(Clone, Copy, Default)]
+pub struct RangeChminChmaxAddSum;
+
+impl Monoid for RangeChminChmaxAddSum {
+    type S = BeatsNode;
+    fn e(&self) -> BeatsNode {
+        BeatsNode::EMPTY
+    }
+    fn op(&self, a: BeatsNode, b: BeatsNode) -> BeatsNode {
+        if a.len == 0 {
+            return b;
+        }
+        if b.len == 0 {
+            return a;
+        }
+        let (min, max) = (a.min.min(b.min), a.max.max(b.max));
+        let min2 = match a.min.cmp(&b.min) {
+            std::cmp::Ordering::Less => a.min2.min(b.min),
+            std::cmp::Ordering::Equal => a.min2.min(b.min2),
+            std::cmp::Ordering::Greater => a.min.min(b.min2),
+        };
+        let max2 = match a.max.cmp(&b.max) {
+            std::cmp::Ordering::Greater => a.max2.max(b.max),
+            std::cmp::Ordering::Equal => a.max2.max(b.max2),
+            std::cmp::Ordering::Less => a.max.max(b.max2),
+        };
+        BeatsNode {
+            min,
+            max,
+            sum: a.sum + b.sum,
+            min2,
+            max2,
+            cnt_min: if a.min == min { a.cnt_min } else { 0 } + if b.min == min { b.cnt_min } else { 0 },
+            cnt_max: if a.max == max { a.cnt_max } else { 0 } + if b.max == max { b.cnt_max } else { 0 },
+            len: a.len + b.len,
+            fail: false,
+        }
+    }
+}
+
+impl MapMonoid for RangeChminChmaxAddSum {
+    type F = BeatsMap;
+    fn id(&self) -> BeatsMap {
+        BeatsMap::add(0)
+    }
+    fn mapping(&self, x: &mut BeatsNode, f: BeatsMap) {
+        if x.len == 0 {
+            return;
+        }
+        if x.min == x.max || f.lb == f.ub || f.lb >= x.max || f.ub <= x.min {
+            *x = BeatsNode::uniform(x.min.max(f.lb).min(f.ub) + f.bias, x.len);
+        } else if x.min2 == x.max {
+            x.min = x.min.max(f.lb) + f.bias;
+            x.max = x.max.min(f.ub) + f.bias;
+            x.max2 = x.min;
+            x.min2 = x.max;
+            x.sum = x.min * x.cnt_min + x.max * x.cnt_max;
+        } else if f.lb < x.min2 && x.max2 < f.ub {
+            let min = x.min.max(f.lb);
+            let max = x.max.min(f.ub);
+            x.sum += (min - x.min) * x.cnt_min - (x.max - max) * x.cnt_max + f.bias * x.len;
+            x.min = min + f.bias;
+            x.max = max + f.bias;
+            x.min2 += f.bias;
+            x.max2 += f.bias;
+        } else {
+            x.fail = true;
+        }
+    }
+    fn composition(&self, f: &mut BeatsMap, g: BeatsMap) {
+        let lb = g.lb.saturating_sub(f.bias);
+        let ub = g.ub.saturating_sub(f.bias);
+        *f = BeatsMap { lb: f.lb.max(lb).min(ub), ub: f.ub.max(lb).min(ub), bias: f.bias + g.bias };
+    }
+    fn fails(&self, x: &BeatsNode) -> bool {
+        x.fail
+    }
+}
+
+#[test]
+fn test() {
+    use super::LazySeg;
+    let mut rng = crate::etc::Xrsr::with_seed(0x2545_f491_4f6c_dd1d);
+    let n = 37;
+    let mut naive = (0..n).map(|_| (rng.gen() % 201) as i64 - 100).collect::<Vec<_>>();
+    let mut seg = LazySeg::with_monoid(n, RangeChminChmaxAddSum);
+    for (i, &x) in naive.iter().enumerate() {
+        seg[i] = BeatsNode::new(x);
+    }
+    seg.build();
+    for _ in 0..2000 {
+        let l = rng.gen() % n;
+        let r = l + 1 + rng.gen() % (n - l);
+        let x = (rng.gen() % 201) as i64 - 100;
+        match rng.gen() % 4 {
+            0 => {
+                seg.apply(l, r, BeatsMap::chmin(x));
+                naive[l..r].iter_mut().for_each(|v| *v = (*v).min(x));
+            }
+            1 => {
+                seg.apply(l, r, BeatsMap::chmax(x));
+                naive[l..r].iter_mut().for_each(|v| *v = (*v).max(x));
+            }
+            2 => {
+                seg.apply(l, r, BeatsMap::add(x));
+                naive[l..r].iter_mut().for_each(|v| *v += x);
+            }
+            _ => {
+                let node = seg.product(l, r);
+                assert_eq!(node.sum, naive[l..r].iter().sum::<i64>());
+                assert_eq!(node.min, *naive[l..r].iter().min().unwrap());
+                assert_eq!(node.max, *naive[l..r].iter().max().unwrap());
+            }
+        }
+    }
+}
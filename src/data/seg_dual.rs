@@ -0,0 +1,106 @@
+//! Dual segment tree
+//!
+//! Range apply, point get. Only the tags of [`super::LazySeg`] are kept, without any data or `op` on it.
+//!
+//! The tags form a [`Monoid`] where `op(f, g)` applies `f` first and then `g`, and [`DualSegmentTree::get`] returns
+//! the composition of all the tags applied to the position, to be applied to the initial value by the caller.
+//!
+//! Tags of the ancestors are pushed down before applying, so a tag is always older than the tags of its ancestors.
+//! If the composition is commutative, the push can be skipped with [`DualSegmentTree::with_commutative`].
+//!
+//! # Time Complexity
+//!
+//! O(log n) per operation.
+
+use super::algebra::{CommutativeMonoid, FnMonoid, Monoid};
+
+pub struct DualSegmentTree<M: Monoid> {
+    size: usize,
+    height: u32,
+    lazy: Box<[M::S]>,
+    push: bool,
+    m: M,
+}
+
+impl<T, F> DualSegmentTree<FnMonoid<T, F>>
+where
+    T: Copy,
+    F: Fn(T, T) -> T,
+{
+    pub fn new(size: usize, id: T, composition: F) -> Self {
+        Self::with_monoid(size, FnMonoid::new(id, composition))
+    }
+}
+
+impl<M: CommutativeMonoid> DualSegmentTree<M> {
+    pub fn with_commutative(size: usize, m: M) -> Self {
+        Self { push: false, ..Self::with_monoid(size, m) }
+    }
+}
+
+impl<M: Monoid> DualSegmentTree<M> {
+    pub fn with_monoid(size: usize, m: M) -> Self {
+        let size = size.next_power_of_two();
+        let height = size.trailing_zeros() + 1;
+        Self { size, height, lazy: vec![m.e(); size << 1].into(), push: true, m }
+    }
+    /// The composition of the tags applied to `i`, from the oldest.
+    pub fn get(&self, i: usize) -> M::S {
+        assert!(i < self.size);
+        let i = i + self.size;
+        (1..self.height).fold(self.lazy[i], |acc, k| self.m.op(acc, self.lazy[i >> k]))
+    }
+    pub fn apply(&mut self, range: std::ops::Range<usize>, f: M::S) {
+        assert!(range.start <= range.end && range.end <= self.size);
+        if range.is_empty() {
+            return;
+        }
+        let (mut l, mut r) = (range.start + self.size, range.end + self.size);
+        if self.push {
+            (l.trailing_zeros() + 1..self.height).rev().for_each(|k| self._push(l >> k));
+            (r.trailing_zeros() + 1..self.height).rev().for_each(|k| self._push((r - 1) >> k));
+        }
+        while l != r {
+            if l & 1 == 1 {
+                self._all_apply(l, f);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                self._all_apply(r, f);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+    }
+    fn _all_apply(&mut self, i: usize, f: M::S) {
+        self.lazy[i] = self.m.op(self.lazy[i], f);
+    }
+    fn _push(&mut self, i: usize) {
+        let f = std::mem::replace(&mut self.lazy[i], self.m.e());
+        self._all_apply(i << 1, f);
+        self._all_apply(i << 1 | 1, f);
+    }
+}
+
+#[test]
+fn test() {
+    // x <- a * x + b, non-commutative
+    let mut affine =
+        DualSegmentTree::new(10, (1_i64, 0_i64), |f: (i64, i64), g: (i64, i64)| (g.0 * f.0, g.0 * f.1 + g.1));
+    let mut add = DualSegmentTree::with_commutative(10, super::Additive::<i64>::default());
+    let init = [3_i64, 1, 4, 1, 5, 9, 2, 6, 5, 3];
+    let mut naive = init;
+    let mut naive_add = [0_i64; 10];
+    for (l, r, a, b) in [(0, 10, 2, 1), (3, 7, -1, 4), (5, 6, 3, -2), (0, 4, 1, 5), (2, 9, -2, 0)] {
+        affine.apply(l..r, (a, b));
+        add.apply(l..r, b);
+        naive[l..r].iter_mut().for_each(|x| *x = a * *x + b);
+        naive_add[l..r].iter_mut().for_each(|x| *x += b);
+        for i in 0..10 {
+            let (a, b) = affine.get(i);
+            assert_eq!(a * init[i] + b, naive[i]);
+            assert_eq!(add.get(i), naive_add[i]);
+        }
+    }
+}
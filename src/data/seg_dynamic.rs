@@ -0,0 +1,279 @@
+//! Dynamic segment tree
+//!
+//! Segment trees over a huge coordinate range such as `-10^18..10^18`, whose nodes are created on demand.
+//! Nodes live in an arena (`Vec`) and refer to their children by index, `0` meaning _absent_ (the root is never a child).
+//!
+//! The domain is rounded up to a power of two, so every node covers `2^d` coordinates.
+//! Only the coordinates in the requested range are accepted, not the ones in the padding.
+//!
+//! # Time Complexity
+//!
+//! O(log C) per operation, creating at most O(log C) nodes, where C is the length of the domain.
+
+use super::algebra::{FnMonoid, MapMonoid, Monoid};
+
+struct Node<S> {
+    val: S,
+    ch: [u32; 2],
+}
+
+pub struct DynamicSegmentTree<M: Monoid> {
+    range: std::ops::Range<i64>,
+    height: u32,
+    nodes: Vec<Node<M::S>>,
+    m: M,
+}
+
+impl<T, F> DynamicSegmentTree<FnMonoid<T, F>>
+where
+    T: Copy,
+    F: Fn(T, T) -> T,
+{
+    pub fn new(range: std::ops::Range<i64>, e: T, f: F) -> Self {
+        Self::with_monoid(range, FnMonoid::new(e, f))
+    }
+}
+
+impl<M: Monoid> DynamicSegmentTree<M> {
+    pub fn with_monoid(range: std::ops::Range<i64>, m: M) -> Self {
+        assert!(range.start < range.end);
+        let height = height(range.start.abs_diff(range.end));
+        let nodes = vec![Node { val: m.e(), ch: [0; 2] }];
+        Self { range, height, nodes, m }
+    }
+    pub fn get(&self, i: i64) -> M::S {
+        let pos = offset(&self.range, i);
+        let mut u = 0;
+        for d in (0..self.height).rev() {
+            u = self.nodes[u].ch[(pos >> d & 1) as usize] as usize;
+            if u == 0 {
+                return self.m.e();
+            }
+        }
+        self.nodes[u].val
+    }
+    pub fn set(&mut self, i: i64, x: M::S) {
+        let pos = offset(&self.range, i);
+        self._set(0, self.height, pos, x);
+    }
+    pub fn prod(&self, range: std::ops::Range<i64>) -> M::S {
+        if range.start >= range.end {
+            return self.m.e();
+        }
+        let l = offset(&self.range, range.start);
+        let r = offset(&self.range, range.end - 1) + 1;
+        self._prod(0, self.height, 0, l, r)
+    }
+    fn _set(&mut self, u: usize, d: u32, pos: u64, x: M::S) {
+        if d == 0 {
+            self.nodes[u].val = x;
+            return;
+        }
+        let b = (pos >> (d - 1) & 1) as usize;
+        if self.nodes[u].ch[b] == 0 {
+            self.nodes[u].ch[b] = self.nodes.len() as u32;
+            self.nodes.push(Node { val: self.m.e(), ch: [0; 2] });
+        }
+        self._set(self.nodes[u].ch[b] as usize, d - 1, pos, x);
+        let [c0, c1] = self.nodes[u].ch.map(|c| if c == 0 { self.m.e() } else { self.nodes[c as usize].val });
+        self.nodes[u].val = self.m.op(c0, c1);
+    }
+    fn _prod(&self, u: usize, d: u32, l: u64, ql: u64, qr: u64) -> M::S {
+        let r = l + (1 << d);
+        if qr <= l || r <= ql {
+            return self.m.e();
+        }
+        if ql <= l && r <= qr {
+            return self.nodes[u].val;
+        }
+        let half = 1 << (d - 1);
+        let [c0, c1] = self.nodes[u].ch;
+        let p0 = if c0 == 0 { self.m.e() } else { self._prod(c0 as usize, d - 1, l, ql, qr) };
+        let p1 = if c1 == 0 { self.m.e() } else { self._prod(c1 as usize, d - 1, l + half, ql, qr) };
+        self.m.op(p0, p1)
+    }
+}
+
+struct LazyNode<S, F> {
+    val: S,
+    lazy: F,
+    ch: [u32; 2],
+}
+
+/// Every coordinate initially holds `leaf`. A node covering `2^d` coordinates starts as `leaf^(2^d)`.
+pub struct DynamicLazySeg<M: MapMonoid> {
+    range: std::ops::Range<i64>,
+    height: u32,
+    nodes: Vec<LazyNode<M::S, M::F>>,
+    pow: Box<[M::S]>,
+    m: M,
+}
+
+impl<M: MapMonoid> DynamicLazySeg<M> {
+    pub fn with_monoid(range: std::ops::Range<i64>, m: M, leaf: M::S) -> Self {
+        assert!(range.start < range.end);
+        let height = height(range.start.abs_diff(range.end));
+        let pow =
+            std::iter::successors(Some(leaf), |&x| Some(m.op(x, x))).take(height as usize + 1).collect::<Box<_>>();
+        let nodes = vec![LazyNode { val: pow[height as usize], lazy: m.id(), ch: [0; 2] }];
+        Self { range, height, nodes, pow, m }
+    }
+    pub fn get(&mut self, i: i64) -> M::S {
+        self.product(i, i + 1)
+    }
+    pub fn set(&mut self, i: i64, x: M::S) {
+        let pos = offset(&self.range, i);
+        self._set(0, self.height, pos, x);
+    }
+    pub fn apply(&mut self, l: i64, r: i64, f: M::F) {
+        if l >= r {
+            return;
+        }
+        let ql = offset(&self.range, l);
+        let qr = offset(&self.range, r - 1) + 1;
+        self._apply(0, self.height, 0, ql, qr, f);
+    }
+    pub fn product(&mut self, l: i64, r: i64) -> M::S {
+        if l >= r {
+            return self.m.e();
+        }
+        let ql = offset(&self.range, l);
+        let qr = offset(&self.range, r - 1) + 1;
+        self._product(0, self.height, 0, ql, qr)
+    }
+    fn _set(&mut self, u: usize, d: u32, pos: u64, x: M::S) {
+        if d == 0 {
+            self.nodes[u].val = x;
+            return;
+        }
+        self._push(u, d);
+        let c = self.nodes[u].ch[(pos >> (d - 1) & 1) as usize];
+        self._set(c as usize, d - 1, pos, x);
+        self._pull(u);
+    }
+    fn _apply(&mut self, u: usize, d: u32, l: u64, ql: u64, qr: u64, f: M::F) {
+        let r = l + (1 << d);
+        if qr <= l || r <= ql {
+            return;
+        }
+        if ql <= l && r <= qr {
+            self._all_apply(u, d, f);
+            return;
+        }
+        self._push(u, d);
+        let [c0, c1] = self.nodes[u].ch;
+        self._apply(c0 as usize, d - 1, l, ql, qr, f);
+        self._apply(c1 as usize, d - 1, l + (1 << (d - 1)), ql, qr, f);
+        self._pull(u);
+    }
+    fn _product(&mut self, u: usize, d: u32, l: u64, ql: u64, qr: u64) -> M::S {
+        let r = l + (1 << d);
+        if qr <= l || r <= ql {
+            return self.m.e();
+        }
+        if ql <= l && r <= qr {
+            return self.nodes[u].val;
+        }
+        self._push(u, d);
+        let [c0, c1] = self.nodes[u].ch;
+        let p0 = self._product(c0 as usize, d - 1, l, ql, qr);
+        let p1 = self._product(c1 as usize, d - 1, l + (1 << (d - 1)), ql, qr);
+        self.m.op(p0, p1)
+    }
+    fn _pull(&mut self, u: usize) {
+        let [c0, c1] = self.nodes[u].ch;
+        self.nodes[u].val = self.m.op(self.nodes[c0 as usize].val, self.nodes[c1 as usize].val);
+    }
+    /// Creates the children if absent, and pushes the pending map down to them.
+    fn _push(&mut self, u: usize, d: u32) {
+        if self.nodes[u].ch[0] == 0 {
+            for b in 0..2 {
+                self.nodes[u].ch[b] = self.nodes.len() as u32;
+                self.nodes.push(LazyNode { val: self.pow[d as usize - 1], lazy: self.m.id(), ch: [0; 2] });
+            }
+        }
+        let f = std::mem::replace(&mut self.nodes[u].lazy, self.m.id());
+        let [c0, c1] = self.nodes[u].ch;
+        self._all_apply(c0 as usize, d - 1, f);
+        self._all_apply(c1 as usize, d - 1, f);
+    }
+    fn _all_apply(&mut self, u: usize, d: u32, f: M::F) {
+        self.m.mapping(&mut self.nodes[u].val, f);
+        if d != 0 {
+            self.m.composition(&mut self.nodes[u].lazy, f);
+            if self.m.fails(&self.nodes[u].val) {
+                self._push(u, d);
+                self._pull(u);
+            }
+        }
+    }
+}
+
+/// The smallest `h` such that `2^h >= len`.
+fn height(len: u64) -> u32 {
+    assert!(len <= 1 << 62);
+    len.next_power_of_two().trailing_zeros()
+}
+
+/// The position of `i` from the start of the domain, which must contain `i`.
+fn offset(range: &std::ops::Range<i64>, i: i64) -> u64 {
+    assert!(range.contains(&i));
+    i.wrapping_sub(range.start) as u64
+}
+
+#[test]
+fn dynamic_segment_tree() {
+    let mut seg =
+        DynamicSegmentTree::new(-1_000_000_000_000_000_000..1_000_000_000_000_000_000, 0_i64, std::ops::Add::add);
+    let mut naive = std::collections::BTreeMap::new();
+    let points =
+        [(-999_999_999_999_999_999, 5), (0, -2), (7, 3), (123_456_789_012, 10), (0, 4), (999_999_999_999_999_999, 1)];
+    for (i, x) in points {
+        seg.set(i, x);
+        naive.insert(i, x);
+        let keys = naive
+            .keys()
+            .copied()
+            .chain([-1_000_000_000_000_000_000, -1, 1, 8, 999_999_999_999_999_999])
+            .collect::<Vec<_>>();
+        for &l in keys.iter() {
+            for &r in keys.iter() {
+                let expected = if l < r { naive.range(l..r).map(|(_, &x)| x).sum() } else { 0 };
+                assert_eq!(seg.prod(l..r), expected);
+            }
+            assert_eq!(seg.get(l), naive.get(&l).copied().unwrap_or(0));
+        }
+    }
+}
+
+#[test]
+fn dynamic_lazy_seg() {
+    use super::RangeAddSum;
+    let mut seg = DynamicLazySeg::with_monoid(-40..50, RangeAddSum::default(), (1_i64, 1));
+    let mut naive = [1_i64; 90];
+    for (l, r, f) in [(-40, 50, 2), (-3, 10, -5), (20, 21, 7), (-40, -39, 1), (0, 45, 3)] {
+        seg.apply(l, r, f);
+        naive[(l + 40) as usize..(r + 40) as usize].iter_mut().for_each(|x| *x += f);
+        for r in -40..=50 {
+            for l in -40..=r {
+                let expected = naive[(l + 40) as usize..(r + 40) as usize].iter().sum::<i64>();
+                assert_eq!(seg.product(l, r), (expected, r - l));
+            }
+        }
+    }
+    seg.set(4, (100, 1));
+    assert_eq!(seg.get(4), (100, 1));
+    let mut huge = DynamicLazySeg::with_monoid(0..1 << 60, RangeAddSum::default(), (0_i64, 1));
+    huge.apply(1 << 40, 1 << 50, 1);
+    huge.apply(0, 1 << 45, 2);
+    assert_eq!(huge.product(0, 1 << 60).0, (1 << 50) - (1 << 40) + (2 << 45));
+    assert_eq!(huge.get((1 << 45) - 1), (3, 1));
+}
+
+#[test]
+#[should_panic]
+fn outside_domain() {
+    // the domain is padded to 0..8 internally
+    let mut seg = DynamicSegmentTree::new(0..5, 0, std::ops::Add::add);
+    seg.set(5, 1);
+}
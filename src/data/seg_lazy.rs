@@ -1,29 +1,33 @@
-struct LazySeg<T, U, F1, F2, F3> {
+use super::algebra::{FnMapMonoid, MapMonoid};
+
+pub struct LazySeg<M: MapMonoid> {
+    /// The requested size, before padding to `size`.
+    n: usize,
     size: usize,
     height: u32,
-    tree: Box<[T]>,
-    lazy: Box<[U]>,
-    e: T,
-    off: U,
-    op: F1,
-    mapping: F2,
-    composition: F3,
+    tree: Box<[M::S]>,
+    lazy: Box<[M::F]>,
+    m: M,
 }
 
-impl<T, U, F1, F2, F3> std::ops::Index<usize> for LazySeg<T, U, F1, F2, F3> {
-    type Output = T;
-    fn index(&self, i: usize) -> &T {
+/// Raw access to the leaves, for filling them before [`LazySeg::build`].
+///
+/// Pending tags of the ancestors are not applied, so use [`LazySeg::get`] and [`LazySeg::set`] in between.
+/// Refilling all the leaves and calling [`LazySeg::build`] again starts over.
+impl<M: MapMonoid> std::ops::Index<usize> for LazySeg<M> {
+    type Output = M::S;
+    fn index(&self, i: usize) -> &M::S {
         &self.tree[i + self.size]
     }
 }
 
-impl<T, U, F1, F2, F3> std::ops::IndexMut<usize> for LazySeg<T, U, F1, F2, F3> {
-    fn index_mut(&mut self, i: usize) -> &mut T {
+impl<M: MapMonoid> std::ops::IndexMut<usize> for LazySeg<M> {
+    fn index_mut(&mut self, i: usize) -> &mut M::S {
         &mut self.tree[i + self.size]
     }
 }
 
-impl<T, U, F1, F2, F3> LazySeg<T, U, F1, F2, F3>
+impl<T, U, F1, F2, F3> LazySeg<FnMapMonoid<T, U, F1, F2, F3>>
 where
     T: Copy,
     U: Copy,
@@ -31,25 +35,41 @@ where
     F2: Fn(&mut T, U),
     F3: Fn(&mut U, U),
 {
-    fn new(size: usize, e: T, off: U, op: F1, mapping: F2, composition: F3) -> Self {
-        let size = size.next_power_of_two();
+    pub fn new(size: usize, e: T, off: U, op: F1, mapping: F2, composition: F3) -> Self {
+        Self::with_monoid(size, FnMapMonoid::new(e, off, op, mapping, composition))
+    }
+}
+
+impl<M: MapMonoid> LazySeg<M> {
+    /// The leaves start as [`MapMonoid::init`], and the padding as [`super::algebra::Monoid::e`].
+    pub fn with_monoid(n: usize, m: M) -> Self {
+        let size = n.next_power_of_two();
         let height = size.trailing_zeros() + 1;
-        LazySeg {
-            size,
-            height,
-            tree: vec![e; size << 1].into(),
-            lazy: vec![off; size].into(),
-            e,
-            off,
-            op,
-            mapping,
-            composition,
-        }
-    }
-    fn build(&mut self) {
+        let mut tree = vec![m.e(); size << 1];
+        tree[size..size + n].fill(m.init());
+        let mut seg = LazySeg { n, size, height, tree: tree.into(), lazy: vec![m.id(); size].into(), m };
+        seg.build();
+        seg
+    }
+    /// Recomputes the inner nodes from the leaves, dropping the pending tags.
+    pub fn build(&mut self) {
+        self.lazy.fill(self.m.id());
         (1..self.size).rev().for_each(|i| self._pull(i));
     }
-    fn apply(&mut self, mut l: usize, mut r: usize, f: U) {
+    pub fn get(&mut self, i: usize) -> M::S {
+        assert!(i < self.size);
+        let i = i + self.size;
+        (1..self.height).rev().for_each(|k| self._push(i >> k));
+        self.tree[i]
+    }
+    pub fn set(&mut self, i: usize, x: M::S) {
+        assert!(i < self.size);
+        let i = i + self.size;
+        (1..self.height).rev().for_each(|k| self._push(i >> k));
+        self.tree[i] = x;
+        (1..self.height).for_each(|k| self._pull(i >> k));
+    }
+    pub fn apply(&mut self, mut l: usize, mut r: usize, f: M::F) {
         assert!(l <= r && r <= self.size);
         if l == r {
             return;
@@ -77,42 +97,191 @@ where
         anc_l.for_each(|i| self._pull(i));
         anc_r.for_each(|i| self._pull(i));
     }
-    fn product(&mut self, mut l: usize, mut r: usize) -> T {
+    pub fn product(&mut self, mut l: usize, mut r: usize) -> M::S {
         assert!(l <= r && r <= self.size);
         if l == r {
-            return self.e;
+            return self.m.e();
         }
         l += self.size;
         r += self.size;
         (l.trailing_zeros() + 1..self.height).rev().for_each(|i| self._push(l >> i));
         (r.trailing_zeros() + 1..self.height).rev().for_each(|i| self._push((r - 1) >> i));
-        let mut res = (self.e, self.e);
+        let mut res = (self.m.e(), self.m.e());
         while l != r {
             if l & 1 == 1 {
-                res.0 = (self.op)(res.0, self.tree[l]);
+                res.0 = self.m.op(res.0, self.tree[l]);
                 l += 1;
             }
             if r & 1 == 1 {
                 r -= 1;
-                res.1 = (self.op)(self.tree[r], res.1);
+                res.1 = self.m.op(self.tree[r], res.1);
             }
             l >>= 1;
             r >>= 1;
         }
-        (self.op)(res.0, res.1)
+        self.m.op(res.0, res.1)
+    }
+    /// Returns the largest `r <= n` such that `pred(product(l, r))` holds,
+    /// assuming `pred` is monotone and `pred(e)` is true.
+    pub fn max_right<P: Fn(M::S) -> bool>(&mut self, l: usize, pred: P) -> usize {
+        assert!(l <= self.n && pred(self.m.e()));
+        if l == self.n {
+            return self.n;
+        }
+        let mut l = l + self.size;
+        (1..self.height).rev().for_each(|i| self._push(l >> i));
+        let mut acc = self.m.e();
+        loop {
+            l >>= l.trailing_zeros();
+            if !pred(self.m.op(acc, self.tree[l])) {
+                while l < self.size {
+                    self._push(l);
+                    l <<= 1;
+                    let next = self.m.op(acc, self.tree[l]);
+                    if pred(next) {
+                        acc = next;
+                        l += 1;
+                    }
+                }
+                return (l - self.size).min(self.n);
+            }
+            acc = self.m.op(acc, self.tree[l]);
+            l += 1;
+            if l.is_power_of_two() {
+                return self.n;
+            }
+        }
+    }
+    /// Returns the smallest `l` such that `pred(product(l, r))` holds, assuming `pred` is monotone and `pred(e)` is true.
+    pub fn min_left<P: Fn(M::S) -> bool>(&mut self, r: usize, pred: P) -> usize {
+        assert!(r <= self.n && pred(self.m.e()));
+        if r == 0 {
+            return 0;
+        }
+        let mut r = r + self.size;
+        (1..self.height).rev().for_each(|i| self._push((r - 1) >> i));
+        let mut acc = self.m.e();
+        loop {
+            r -= 1;
+            while r > 1 && r & 1 == 1 {
+                r >>= 1;
+            }
+            if !pred(self.m.op(self.tree[r], acc)) {
+                while r < self.size {
+                    self._push(r);
+                    r = r << 1 | 1;
+                    let next = self.m.op(self.tree[r], acc);
+                    if pred(next) {
+                        acc = next;
+                        r -= 1;
+                    }
+                }
+                return r + 1 - self.size;
+            }
+            acc = self.m.op(self.tree[r], acc);
+            if r.is_power_of_two() {
+                return 0;
+            }
+        }
     }
     fn _pull(&mut self, i: usize) {
-        self.tree[i] = (self.op)(self.tree[i << 1], self.tree[i << 1 | 1]);
+        self.tree[i] = self.m.op(self.tree[i << 1], self.tree[i << 1 | 1]);
     }
     fn _push(&mut self, i: usize) {
         self._all_apply(i << 1, self.lazy[i]);
         self._all_apply(i << 1 | 1, self.lazy[i]);
-        self.lazy[i] = self.off;
+        self.lazy[i] = self.m.id();
     }
-    fn _all_apply(&mut self, i: usize, f: U) {
-        (self.mapping)(&mut self.tree[i], f);
+    fn _all_apply(&mut self, i: usize, f: M::F) {
+        self.m.mapping(&mut self.tree[i], f);
         if i & self.size == 0 {
-            (self.composition)(&mut self.lazy[i], f);
+            self.m.composition(&mut self.lazy[i], f);
+            if self.m.fails(&self.tree[i]) {
+                self._push(i);
+                self._pull(i);
+            }
+        }
+    }
+}
+
+#[test]
+fn range_add_range_min() {
+    use super::algebra::Monoid;
+    struct AddMin;
+    impl Monoid for AddMin {
+        type S = i64;
+        fn e(&self) -> i64 {
+            i64::MAX
+        }
+        fn op(&self, a: i64, b: i64) -> i64 {
+            a.min(b)
+        }
+    }
+    impl MapMonoid for AddMin {
+        type F = i64;
+        fn id(&self) -> i64 {
+            0
+        }
+        fn mapping(&self, x: &mut i64, f: i64) {
+            *x = x.saturating_add(f);
+        }
+        fn composition(&self, f: &mut i64, g: i64) {
+            *f += g;
+        }
+    }
+    let mut naive = [5, 3, 8, 1, 9, 2, 7];
+    let mut seg = LazySeg::with_monoid(naive.len(), AddMin);
+    for (i, &x) in naive.iter().enumerate() {
+        seg[i] = x;
+    }
+    seg.build();
+    for (l, r, f) in [(0, 3, 2), (2, 7, -4), (1, 2, 10), (4, 6, 1)] {
+        seg.apply(l, r, f);
+        naive[l..r].iter_mut().for_each(|x| *x += f);
+        for j in 0..=naive.len() {
+            for i in 0..=j {
+                assert_eq!(naive[i..j].iter().copied().min().unwrap_or(i64::MAX), seg.product(i, j));
+            }
+        }
+    }
+}
+
+#[test]
+fn point_access_and_binary_search() {
+    let mut naive = vec![4, 2, 7, 1, 3, 6, 5, 2, 8];
+    let n = naive.len();
+    let mut seg = LazySeg::new(n, i64::MAX, 0, std::cmp::min, |x, f| *x = x.saturating_add(f), |g, f| *g += f);
+    for (i, &x) in naive.iter().enumerate() {
+        seg[i] = x;
+    }
+    seg.build();
+    for (l, r, f, i, x) in [(0, 5, -2, 3, 9), (3, 9, -3, 0, -1), (1, 4, 1, 8, 0), (0, 9, -1, 5, 2)] {
+        seg.apply(l, r, f);
+        naive[l..r].iter_mut().for_each(|v| *v += f);
+        assert!((0..n).all(|j| seg.get(j) == naive[j]));
+        seg.set(i, x);
+        naive[i] = x;
+        // the first position where the value drops below zero, starting from `l`
+        for l in 0..=n {
+            let r = seg.max_right(l, |v| v >= 0);
+            assert_eq!(r, (l..n).find(|&j| naive[j] < 0).unwrap_or(n));
+        }
+        for r in 0..=n {
+            let l = seg.min_left(r, |v| v >= 0);
+            assert_eq!(l, (0..r).rev().find(|&j| naive[j] < 0).map_or(0, |j| j + 1));
         }
     }
 }
+
+#[test]
+fn rebuild() {
+    let mut seg = LazySeg::new(4, i64::MIN, 0, std::cmp::max, |x, f| *x += f, |g, f| *g += f);
+    seg[1] = 5;
+    seg.build();
+    seg.apply(0, 2, 10);
+    assert_eq!(seg.product(0, 4), 15);
+    (0..4).for_each(|i| seg[i] = i as i64);
+    seg.build();
+    assert_eq!(seg.product(0, 4), 3);
+    assert_eq!(seg.get(1), 1);
+}
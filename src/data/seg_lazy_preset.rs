@@ -0,0 +1,434 @@
+//! Lazy segment tree presets
+//!
+//! Ready-made [`MapMonoid`]s for [`super::LazySeg::with_monoid`].
+//!
+//! [`LazySeg::with_monoid`] starts every preset with `n` zeros, and [`LazySeg::from_values`] with the given values.
+//!
+//! Nodes of the sum presets are `(sum, len)` pairs, and every leaf has length one ([`MapMonoid::init`]).
+//! Padding leaves keep length zero, so they never contribute to a sum.
+
+use super::algebra::{Bounded, MapMonoid, Monoid};
+use super::LazySeg;
+use crate::math::ModNum;
+use std::marker::PhantomData;
+use std::ops::{Add, Mul};
+
+/// Range add, range sum.
+#[derive(Clone, Copy, Default)]
+pub struct RangeAddSum<T>(PhantomData<T>);
+
+impl<T> Monoid for RangeAddSum<T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    type S = (T, T);
+    fn e(&self) -> (T, T) {
+        (T::default(), T::default())
+    }
+    fn op(&self, a: (T, T), b: (T, T)) -> (T, T) {
+        (a.0 + b.0, a.1 + b.1)
+    }
+}
+
+impl<T> MapMonoid for RangeAddSum<T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + From<u8>,
+{
+    type F = T;
+    fn id(&self) -> T {
+        T::default()
+    }
+    fn mapping(&self, x: &mut (T, T), f: T) {
+        x.0 = x.0 + f * x.1;
+    }
+    fn composition(&self, f: &mut T, g: T) {
+        *f = *f + g;
+    }
+    fn init(&self) -> (T, T) {
+        (T::default(), T::from(1))
+    }
+}
+
+impl<T> Leaf for RangeAddSum<T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + From<u8>,
+{
+    type V = T;
+    fn leaf(&self, x: T) -> (T, T) {
+        (x, T::from(1))
+    }
+}
+
+/// Range add, range min.
+#[derive(Clone, Copy, Default)]
+pub struct RangeAddMin<T>(PhantomData<T>);
+
+impl<T: Bounded> Monoid for RangeAddMin<T> {
+    type S = T;
+    fn e(&self) -> T {
+        T::MAX
+    }
+    fn op(&self, a: T, b: T) -> T {
+        a.min(b)
+    }
+}
+
+impl<T: Bounded + Default + Add<Output = T>> MapMonoid for RangeAddMin<T> {
+    type F = T;
+    fn id(&self) -> T {
+        T::default()
+    }
+    fn mapping(&self, x: &mut T, f: T) {
+        *x = *x + f;
+    }
+    fn composition(&self, f: &mut T, g: T) {
+        *f = *f + g;
+    }
+    fn init(&self) -> T {
+        T::default()
+    }
+}
+
+impl<T: Bounded + Default + Add<Output = T>> Leaf for RangeAddMin<T> {
+    type V = T;
+    fn leaf(&self, x: T) -> T {
+        x
+    }
+}
+
+/// Range add, range max.
+#[derive(Clone, Copy, Default)]
+pub struct RangeAddMax<T>(PhantomData<T>);
+
+impl<T: Bounded> Monoid for RangeAddMax<T> {
+    type S = T;
+    fn e(&self) -> T {
+        T::MIN
+    }
+    fn op(&self, a: T, b: T) -> T {
+        a.max(b)
+    }
+}
+
+impl<T: Bounded + Default + Add<Output = T>> MapMonoid for RangeAddMax<T> {
+    type F = T;
+    fn id(&self) -> T {
+        T::default()
+    }
+    fn mapping(&self, x: &mut T, f: T) {
+        *x = *x + f;
+    }
+    fn composition(&self, f: &mut T, g: T) {
+        *f = *f + g;
+    }
+    fn init(&self) -> T {
+        T::default()
+    }
+}
+
+impl<T: Bounded + Default + Add<Output = T>> Leaf for RangeAddMax<T> {
+    type V = T;
+    fn leaf(&self, x: T) -> T {
+        x
+    }
+}
+
+/// Range assign, range sum.
+#[derive(Clone, Copy, Default)]
+pub struct RangeAssignSum<T>(PhantomData<T>);
+
+impl<T> Monoid for RangeAssignSum<T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    type S = (T, T);
+    fn e(&self) -> (T, T) {
+        (T::default(), T::default())
+    }
+    fn op(&self, a: (T, T), b: (T, T)) -> (T, T) {
+        (a.0 + b.0, a.1 + b.1)
+    }
+}
+
+impl<T> MapMonoid for RangeAssignSum<T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + From<u8>,
+{
+    type F = Option<T>;
+    fn id(&self) -> Option<T> {
+        None
+    }
+    fn mapping(&self, x: &mut (T, T), f: Option<T>) {
+        if let Some(v) = f {
+            x.0 = v * x.1;
+        }
+    }
+    fn composition(&self, f: &mut Option<T>, g: Option<T>) {
+        if g.is_some() {
+            *f = g;
+        }
+    }
+    fn init(&self) -> (T, T) {
+        (T::default(), T::from(1))
+    }
+}
+
+impl<T> Leaf for RangeAssignSum<T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + From<u8>,
+{
+    type V = T;
+    fn leaf(&self, x: T) -> (T, T) {
+        (x, T::from(1))
+    }
+}
+
+/// Range assign, range min.
+#[derive(Clone, Copy, Default)]
+pub struct RangeAssignMin<T>(PhantomData<T>);
+
+impl<T: Bounded> Monoid for RangeAssignMin<T> {
+    type S = T;
+    fn e(&self) -> T {
+        T::MAX
+    }
+    fn op(&self, a: T, b: T) -> T {
+        a.min(b)
+    }
+}
+
+impl<T: Bounded + Default> MapMonoid for RangeAssignMin<T> {
+    type F = Option<T>;
+    fn id(&self) -> Option<T> {
+        None
+    }
+    fn mapping(&self, x: &mut T, f: Option<T>) {
+        if let Some(v) = f {
+            *x = v;
+        }
+    }
+    fn composition(&self, f: &mut Option<T>, g: Option<T>) {
+        if g.is_some() {
+            *f = g;
+        }
+    }
+    fn init(&self) -> T {
+        T::default()
+    }
+}
+
+impl<T: Bounded + Default> Leaf for RangeAssignMin<T> {
+    type V = T;
+    fn leaf(&self, x: T) -> T {
+        x
+    }
+}
+
+/// Range affine `x <- a * x + b`, range sum.
+///
+/// The map is the pair `(a, b)`.
+#[derive(Clone, Copy, Default)]
+pub struct RangeAffineSum<const M: u32>;
+
+impl<const M: u32> Monoid for RangeAffineSum<M> {
+    type S = (ModNum<M>, ModNum<M>);
+    fn e(&self) -> Self::S {
+        (ModNum::new(0), ModNum::new(0))
+    }
+    fn op(&self, a: Self::S, b: Self::S) -> Self::S {
+        (a.0 + b.0, a.1 + b.1)
+    }
+}
+
+impl<const M: u32> MapMonoid for RangeAffineSum<M> {
+    type F = (ModNum<M>, ModNum<M>);
+    fn id(&self) -> Self::F {
+        (ModNum::new(1), ModNum::new(0))
+    }
+    fn mapping(&self, x: &mut Self::S, (a, b): Self::F) {
+        x.0 = a * x.0 + b * x.1;
+    }
+    fn composition(&self, f: &mut Self::F, (a, b): Self::F) {
+        *f = (a * f.0, a * f.1 + b);
+    }
+    fn init(&self) -> Self::S {
+        (ModNum::new(0), ModNum::new(1))
+    }
+}
+
+impl<const M: u32> Leaf for RangeAffineSum<M> {
+    type V = ModNum<M>;
+    fn leaf(&self, x: ModNum<M>) -> Self::S {
+        (x, ModNum::new(1))
+    }
+}
+
+/// Range flip of bits, count of ones.
+///
+/// A node is `(ones, len)`.
+#[derive(Clone, Copy, Default)]
+pub struct RangeFlipCount;
+
+impl Monoid for RangeFlipCount {
+    type S = (usize, usize);
+    fn e(&self) -> (usize, usize) {
+        (0, 0)
+    }
+    fn op(&self, a: (usize, usize), b: (usize, usize)) -> (usize, usize) {
+        (a.0 + b.0, a.1 + b.1)
+    }
+}
+
+impl MapMonoid for RangeFlipCount {
+    type F = bool;
+    fn id(&self) -> bool {
+        false
+    }
+    fn mapping(&self, x: &mut (usize, usize), f: bool) {
+        if f {
+            x.0 = x.1 - x.0;
+        }
+    }
+    fn composition(&self, f: &mut bool, g: bool) {
+        *f ^= g;
+    }
+    fn init(&self) -> (usize, usize) {
+        (0, 1)
+    }
+}
+
+impl Leaf for RangeFlipCount {
+    type V = bool;
+    fn leaf(&self, x: bool) -> (usize, usize) {
+        (x as usize, 1)
+    }
+}
+
+/// Presets whose leaves are made from plain values, such as `(x, 1)` for the sum presets.
+pub trait Leaf: MapMonoid {
+    type V: Copy;
+    fn leaf(&self, x: Self::V) -> Self::S;
+}
+
+impl<M: Leaf> LazySeg<M> {
+    pub fn from_values(values: &[M::V], m: M) -> Self {
+        let leaves = values.iter().map(|&x| m.leaf(x)).collect::<Vec<_>>();
+        let mut seg = LazySeg::with_monoid(values.len(), m);
+        leaves.into_iter().enumerate().for_each(|(i, x)| seg[i] = x);
+        seg.build();
+        seg
+    }
+}
+
+#[test]
+fn range_add() {
+    let mut naive = [3_i64, -1, 4, 1, -5, 9, 2];
+    let n = naive.len();
+    let mut sum = LazySeg::from_values(&naive, RangeAddSum::default());
+    let mut min = LazySeg::from_values(&naive, RangeAddMin::default());
+    let mut max = LazySeg::from_values(&naive, RangeAddMax::default());
+    for (l, r, f) in [(0, 4, 3), (2, 7, -6), (5, 6, 10), (0, 7, 1)] {
+        sum.apply(l, r, f);
+        min.apply(l, r, f);
+        max.apply(l, r, f);
+        naive[l..r].iter_mut().for_each(|x| *x += f);
+        for j in 0..=n {
+            for i in 0..j {
+                assert_eq!(sum.product(i, j), (naive[i..j].iter().sum(), (j - i) as i64));
+                assert_eq!(min.product(i, j), *naive[i..j].iter().min().unwrap());
+                assert_eq!(max.product(i, j), *naive[i..j].iter().max().unwrap());
+            }
+        }
+    }
+}
+
+#[test]
+fn range_assign() {
+    let mut naive = [8_i64, 6, 7, 5, 3, 0, 9];
+    let n = naive.len();
+    let mut sum = LazySeg::from_values(&naive, RangeAssignSum::default());
+    let mut min = LazySeg::from_values(&naive, RangeAssignMin::default());
+    for (l, r, v) in [(1, 5, 4), (3, 7, -2), (0, 2, 1), (2, 3, 11)] {
+        sum.apply(l, r, Some(v));
+        min.apply(l, r, Some(v));
+        naive[l..r].fill(v);
+        for j in 0..=n {
+            for i in 0..j {
+                assert_eq!(sum.product(i, j).0, naive[i..j].iter().sum());
+                assert_eq!(min.product(i, j), *naive[i..j].iter().min().unwrap());
+            }
+        }
+    }
+}
+
+#[test]
+fn range_affine() {
+    type M = ModNum<998_244_353>;
+    let mut naive = (1..=6).map(M::new).collect::<Vec<_>>();
+    let n = naive.len();
+    let mut seg = LazySeg::from_values(&naive, RangeAffineSum);
+    for (l, r, a, b) in [(0, 3, 2, 5), (1, 6, 998_244_352, 7), (2, 4, 3, 0)] {
+        let (a, b) = (M::new(a), M::new(b));
+        seg.apply(l, r, (a, b));
+        naive[l..r].iter_mut().for_each(|x| *x = a * *x + b);
+        for j in 0..=n {
+            for i in 0..j {
+                assert_eq!(seg.product(i, j).0, naive[i..j].iter().fold(M::new(0), |acc, &x| acc + x));
+            }
+        }
+    }
+}
+
+#[test]
+fn range_flip() {
+    let mut naive = [true, false, false, true, true, false, true, false, false];
+    let n = naive.len();
+    let mut seg = LazySeg::from_values(&naive, RangeFlipCount);
+    for (l, r) in [(0, 9), (2, 5), (4, 8), (1, 2)] {
+        seg.apply(l, r, true);
+        naive[l..r].iter_mut().for_each(|x| *x = !*x);
+        for j in 0..=n {
+            for i in 0..j {
+                assert_eq!(seg.product(i, j).0, naive[i..j].iter().filter(|&&x| x).count());
+            }
+        }
+    }
+}
+
+#[test]
+fn zeros() {
+    let mut seg = LazySeg::from_values(&[0_u64; 5], RangeAddSum::default());
+    seg.apply(0, 4, 5);
+    seg.apply(2, 5, 1);
+    assert_eq!(seg.product(0, 5), (23, 5));
+    assert_eq!(seg.product(3, 5), (7, 2));
+}
+
+#[test]
+fn default_leaves() {
+    let mut add = LazySeg::with_monoid(5, RangeAddSum::<i64>::default());
+    let mut assign = LazySeg::with_monoid(5, RangeAssignSum::<i64>::default());
+    let mut min = LazySeg::with_monoid(5, RangeAddMin::<i64>::default());
+    let mut max = LazySeg::with_monoid(5, RangeAddMax::<i64>::default());
+    let mut flip = LazySeg::with_monoid(5, RangeFlipCount);
+    add.apply(1, 4, 3);
+    assign.apply(2, 5, Some(7));
+    min.apply(0, 2, -1);
+    max.apply(3, 5, 2);
+    flip.apply(0, 3, true);
+    assert_eq!(add.product(0, 5), (9, 5));
+    assert_eq!(assign.product(1, 4), (14, 3));
+    assert_eq!((min.product(0, 5), min.product(2, 5)), (-1, 0));
+    assert_eq!((max.product(0, 5), max.product(0, 3)), (2, 0));
+    assert_eq!(flip.product(2, 5), (1, 3));
+}
+
+#[test]
+fn extreme_values() {
+    let mut min = LazySeg::from_values(&[i64::MAX, 0], RangeAddMin::default());
+    let mut max = LazySeg::from_values(&[i64::MIN, 0], RangeAddMax::default());
+    min.apply(0, 1, -1);
+    max.apply(0, 1, 1);
+    assert_eq!(min.product(0, 1), i64::MAX - 1);
+    assert_eq!(max.product(0, 1), i64::MIN + 1);
+}
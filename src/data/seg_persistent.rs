@@ -0,0 +1,201 @@
+//! Persistent segment tree
+//!
+//! Every update copies the path from the root to the leaf, and returns a handle to the new version.
+//! The old versions stay valid, and share all the other nodes with the new one.
+//!
+//! Nodes live in an arena (`Vec`). Node `0` is the empty tree: its value is `e` and both children point to itself.
+//!
+//! # Time Complexity
+//!
+//! O(log n) per operation, creating O(log n) nodes per update.
+
+use super::algebra::{CommutativeMonoid, FnMonoid, Group, Monoid};
+
+/// A handle to one version of a [`PersistentSegmentTree`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Version(u32);
+
+struct Node<S> {
+    val: S,
+    ch: [u32; 2],
+}
+
+pub struct PersistentSegmentTree<M: Monoid> {
+    /// The requested size, before padding to `1 << height`.
+    n: usize,
+    height: u32,
+    nodes: Vec<Node<M::S>>,
+    m: M,
+}
+
+impl<T, F> PersistentSegmentTree<FnMonoid<T, F>>
+where
+    T: Copy,
+    F: Fn(T, T) -> T,
+{
+    pub fn new(size: usize, e: T, f: F) -> Self {
+        Self::with_monoid(size, FnMonoid::new(e, f))
+    }
+}
+
+impl<M: Monoid> PersistentSegmentTree<M> {
+    pub fn with_monoid(n: usize, m: M) -> Self {
+        let height = n.next_power_of_two().trailing_zeros();
+        let nodes = vec![Node { val: m.e(), ch: [0; 2] }];
+        Self { n, height, nodes, m }
+    }
+    /// The version where every element is `e`.
+    pub fn empty(&self) -> Version {
+        Version(0)
+    }
+    /// Builds a new version from `data` in O(n).
+    pub fn build(&mut self, data: &[M::S]) -> Version {
+        assert!(data.len() <= self.n);
+        Version(self._build(self.height, 0, data))
+    }
+    pub fn get(&self, v: Version, i: usize) -> M::S {
+        assert!(i < self.n);
+        let u = (0..self.height).rev().fold(v.0, |u, d| self.nodes[u as usize].ch[i >> d & 1]);
+        self.nodes[u as usize].val
+    }
+    /// Returns the new version, where the `i`-th element of `v` is replaced with `x`.
+    pub fn set(&mut self, v: Version, i: usize, x: M::S) -> Version {
+        assert!(i < self.n);
+        let mut path = [0; usize::BITS as usize];
+        let mut u = v.0;
+        for d in (0..self.height).rev() {
+            path[d as usize] = u;
+            u = self.nodes[u as usize].ch[i >> d & 1];
+        }
+        let mut u = self._push(x, [0; 2]);
+        for d in 0..self.height {
+            let mut ch = self.nodes[path[d as usize] as usize].ch;
+            ch[i >> d & 1] = u;
+            let val = self.m.op(self.nodes[ch[0] as usize].val, self.nodes[ch[1] as usize].val);
+            u = self._push(val, ch);
+        }
+        Version(u)
+    }
+    pub fn prod(&self, v: Version, range: std::ops::Range<usize>) -> M::S {
+        assert!(range.start <= range.end && range.end <= self.n);
+        self._prod(v.0, self.height, 0, range.start, range.end)
+    }
+    fn _push(&mut self, val: M::S, ch: [u32; 2]) -> u32 {
+        self.nodes.push(Node { val, ch });
+        self.nodes.len() as u32 - 1
+    }
+    fn _build(&mut self, d: u32, l: usize, data: &[M::S]) -> u32 {
+        if l >= data.len() {
+            return 0;
+        }
+        if d == 0 {
+            return self._push(data[l], [0; 2]);
+        }
+        let ch = [self._build(d - 1, l, data), self._build(d - 1, l + (1 << (d - 1)), data)];
+        let val = self.m.op(self.nodes[ch[0] as usize].val, self.nodes[ch[1] as usize].val);
+        self._push(val, ch)
+    }
+    fn _prod(&self, u: u32, d: u32, l: usize, ql: usize, qr: usize) -> M::S {
+        let r = l + (1 << d);
+        if u == 0 || qr <= l || r <= ql {
+            return self.m.e();
+        }
+        if ql <= l && r <= qr {
+            return self.nodes[u as usize].val;
+        }
+        let [c0, c1] = self.nodes[u as usize].ch;
+        let half = 1 << (d - 1);
+        self.m.op(self._prod(c0, d - 1, l, ql, qr), self._prod(c1, d - 1, l + half, ql, qr))
+    }
+}
+
+impl<M: CommutativeMonoid + Group> PersistentSegmentTree<M> {
+    /// Returns the largest `r <= n` such that `pred(prod(new, 0..r) - prod(old, 0..r))` holds,
+    /// assuming `pred` is monotone and `pred(e)` is true.
+    pub fn max_right_diff<P: Fn(M::S) -> bool>(&self, old: Version, new: Version, pred: P) -> usize {
+        let diff = |a: u32, b: u32| self.m.op(self.nodes[b as usize].val, self.m.inv(self.nodes[a as usize].val));
+        if pred(diff(old.0, new.0)) {
+            return self.n;
+        }
+        let (mut a, mut b) = (old.0, new.0);
+        let mut acc = self.m.e();
+        let mut pos = 0;
+        for d in (0..self.height).rev() {
+            let [a0, a1] = self.nodes[a as usize].ch;
+            let [b0, b1] = self.nodes[b as usize].ch;
+            let next = self.m.op(acc, diff(a0, b0));
+            if pred(next) {
+                acc = next;
+                pos += 1 << d;
+                (a, b) = (a1, b1);
+            } else {
+                (a, b) = (a0, b0);
+            }
+        }
+        pos.min(self.n)
+    }
+    /// Regarding the elements as counts, returns the position of the `k`-th (0-indexed) item of `new - old`,
+    /// or the size if there are not enough items.
+    ///
+    /// With `old` / `new` being the versions after inserting `a[..l]` / `a[..r]`, it is the `k`-th smallest in `a[l..r]`.
+    pub fn kth(&self, old: Version, new: Version, k: M::S) -> usize
+    where
+        M::S: PartialOrd,
+    {
+        self.max_right_diff(old, new, |s| s <= k)
+    }
+}
+
+#[test]
+fn versions() {
+    let mut seg = PersistentSegmentTree::new(6, 0, std::ops::Add::add);
+    let v0 = seg.build(&[3, 1, 4, 1, 5, 9]);
+    let v1 = seg.set(v0, 2, 10);
+    let v2 = seg.set(v1, 5, 0);
+    let v3 = seg.set(v0, 0, -3);
+    assert_eq!(seg.prod(v0, 0..6), 23);
+    assert_eq!(seg.prod(v1, 0..6), 29);
+    assert_eq!(seg.prod(v2, 2..6), 16);
+    assert_eq!(seg.prod(v3, 0..3), 2);
+    assert_eq!(seg.get(v1, 2), 10);
+    assert_eq!(seg.get(v0, 2), 4);
+    assert_eq!(seg.prod(seg.empty(), 0..6), 0);
+}
+
+#[test]
+fn kth_smallest() {
+    use super::Additive;
+    let a = [5, 1, 4, 1, 5, 9, 2, 6, 5, 3];
+    let mut sorted = a.to_vec();
+    sorted.sort();
+    sorted.dedup();
+    let mut seg = PersistentSegmentTree::with_monoid(sorted.len(), Additive::<u32>::default());
+    let mut roots = vec![seg.empty()];
+    for x in a {
+        let i = sorted.binary_search(&x).unwrap();
+        let root = *roots.last().unwrap();
+        roots.push(seg.set(root, i, seg.get(root, i) + 1));
+    }
+    for l in 0..a.len() {
+        for r in l + 1..=a.len() {
+            let mut naive = a[l..r].to_vec();
+            naive.sort();
+            for (k, &x) in naive.iter().enumerate() {
+                assert_eq!(sorted[seg.kth(roots[l], roots[r], k as u32)], x);
+            }
+            assert_eq!(seg.kth(roots[l], roots[r], (r - l) as u32), sorted.len());
+        }
+    }
+}
+
+#[test]
+fn max_right_diff() {
+    use super::Additive;
+    let mut seg = PersistentSegmentTree::with_monoid(5, Additive::<i64>::default());
+    let v0 = seg.empty();
+    let v1 = seg.build(&[1, 2, 3, 4, 5]);
+    assert_eq!(seg.max_right_diff(v0, v1, |s| s <= 100), 5);
+    assert_eq!(seg.max_right_diff(v0, v1, |s| s <= 6), 3);
+    assert_eq!(seg.max_right_diff(v0, v1, |s| s < 15), 4);
+    assert_eq!(seg.max_right_diff(v1, v1, |s| s <= 0), 5);
+}
@@ -1,21 +1,35 @@
-//! Segment data
+//! Sparse tables
+//!
+//! Static range products in O(1) after O(n log n) preprocessing.
+//!
+//! - [`SparseTable`] covers a range with two overlapping blocks, so it needs an [`Idempotent`] monoid.
+//! - [`DisjointSparseTable`] splits a range at the boundary of two disjoint blocks, so any [`Monoid`] works,
+//!   including non-commutative ones such as [`crate::math::Matrix`] multiplication.
 
-struct SparseTable<T, F> {
+use super::algebra::{FnMonoid, Idempotent, Monoid};
+
+pub struct SparseTable<M: Idempotent> {
     size: usize,
-    data: Box<[Box<[T]>]>,
-    e: T,
-    f: F,
+    data: Box<[Box<[M::S]>]>,
+    m: M,
 }
 
-impl<T, F> SparseTable<T, F>
+/// `f` must be idempotent.
+impl<T, F> SparseTable<FnMonoid<T, F, false, true>>
 where
     T: Copy,
     F: Fn(T, T) -> T,
 {
-    fn new(data: Vec<T>, e: T, f: F) -> Self {
+    pub fn new(data: Vec<T>, e: T, f: F) -> Self {
+        Self::from_monoid(data, FnMonoid::idempotent(e, f))
+    }
+}
+
+impl<M: Idempotent> SparseTable<M> {
+    pub fn from_monoid(data: Vec<M::S>, m: M) -> Self {
         let size = data.len();
         let data = std::iter::successors(Some((data, 1)), |(prev, k)| {
-            let curr = prev.windows(k + 1).map(|w| f(w[0], w[*k])).collect::<Vec<_>>();
+            let curr = prev.windows(k + 1).map(|w| m.op(w[0], w[*k])).collect::<Vec<_>>();
             if curr.is_empty() {
                 None
             } else {
@@ -24,15 +38,67 @@ where
         })
         .map(|x| x.0.into_boxed_slice())
         .collect();
-        Self { size, data, e, f }
+        Self { size, data, m }
     }
-    fn prod(&self, range: std::ops::Range<usize>) -> T {
+    pub fn prod(&self, range: std::ops::Range<usize>) -> M::S {
         assert!(range.start <= range.end && range.end <= self.size);
         if range.is_empty() {
-            return self.e;
+            return self.m.e();
         }
         let k = range.len().ilog2() as usize;
-        (self.f)(self.data[k][range.start], self.data[k][range.end - (1 << k)])
+        self.m.op(self.data[k][range.start], self.data[k][range.end - (1 << k)])
+    }
+}
+
+/// Level `h` splits the array into blocks of `2^(h+1)`, and stores the suffix products of the left half
+/// and the prefix products of the right half of each block. Level `0` is the array itself.
+pub struct DisjointSparseTable<M: Monoid> {
+    size: usize,
+    data: Box<[Box<[M::S]>]>,
+    m: M,
+}
+
+impl<T, F> DisjointSparseTable<FnMonoid<T, F>>
+where
+    T: Copy,
+    F: Fn(T, T) -> T,
+{
+    pub fn new(data: Vec<T>, e: T, f: F) -> Self {
+        Self::from_monoid(data, FnMonoid::new(e, f))
+    }
+}
+
+impl<M: Monoid> DisjointSparseTable<M> {
+    pub fn from_monoid(data: Vec<M::S>, m: M) -> Self {
+        let size = data.len();
+        let mut table = vec![data.into_boxed_slice()];
+        for h in (1..).take_while(|&h| 1 << h < size) {
+            let base = &table[0];
+            let mut curr = base.clone();
+            for s in (0..size).step_by(2 << h) {
+                let mid = (s + (1 << h)).min(size);
+                for i in (s..mid - 1).rev() {
+                    curr[i] = m.op(base[i], curr[i + 1]);
+                }
+                for i in mid + 1..(s + (2 << h)).min(size) {
+                    curr[i] = m.op(curr[i - 1], base[i]);
+                }
+            }
+            table.push(curr);
+        }
+        Self { size, data: table.into_boxed_slice(), m }
+    }
+    pub fn prod(&self, range: std::ops::Range<usize>) -> M::S {
+        assert!(range.start <= range.end && range.end <= self.size);
+        let (l, r) = (range.start, range.end);
+        match range.len() {
+            0 => self.m.e(),
+            1 => self.data[0][l],
+            _ => {
+                let h = (l ^ (r - 1)).ilog2() as usize;
+                self.m.op(self.data[h][l], self.data[h][r - 1])
+            }
+        }
     }
 }
 
@@ -53,3 +119,23 @@ fn test() {
         }
     }
 }
+
+#[test]
+fn disjoint() {
+    let a = (0..13).map(|x| x * x - 5 * x).collect::<Vec<i64>>();
+    let sum = DisjointSparseTable::new(a.clone(), 0, std::ops::Add::add);
+    for j in 0..=a.len() {
+        for i in 0..=j {
+            assert_eq!(a[i..j].iter().sum::<i64>(), sum.prod(i..j));
+        }
+    }
+    use crate::math::Matrix;
+    let id = Matrix::new([[1_i64, 0], [0, 1]]);
+    let a = (0..11_i64).map(|x| Matrix::new([[x % 3, 1], [x - 4, x % 2]])).collect::<Vec<_>>();
+    let prod = DisjointSparseTable::new(a.clone(), id, std::ops::Mul::mul);
+    for j in 0..=a.len() {
+        for i in 0..=j {
+            assert_eq!(a[i..j].iter().fold(id, |acc, &x| acc * x).data, prod.prod(i..j).data);
+        }
+    }
+}
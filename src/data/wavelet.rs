@@ -0,0 +1,323 @@
+//! Wavelet Matrix
+//!
+//! - **Author** &emsp; Francisco Claude, Gonzalo Navarro
+//! - **Source** &emsp; [The Wavelet Matrix](https://doi.org/10.1007/978-3-642-34109-0_18)
+//! - **Update** &emsp; 2026-10-18
+//!
+//! A static structure over an array of `u64`, answering order statistics on any range.
+//!
+//! Level `d` stores the `d`-th highest bit of every value in a [`BitVector`], and then stably moves the values
+//! with the bit unset to the front. Following a range down the levels takes one rank per level.
+//!
+//! # Time Complexity
+//!
+//! O(log V) per query (O(log V log n) for `select`), where V is the maximum value. O(n log V) bits of memory.
+
+/// A bit vector with O(1) rank and O(log n) select.
+pub struct BitVector {
+    len: usize,
+    words: Box<[u64]>,
+    /// Number of ones before each word.
+    rank: Box<[u32]>,
+}
+
+impl BitVector {
+    pub fn new(bits: &[bool]) -> Self {
+        let mut words = vec![0_u64; bits.len() / 64 + 1].into_boxed_slice();
+        for (i, _) in bits.iter().enumerate().filter(|x| *x.1) {
+            words[i >> 6] |= 1 << (i & 63);
+        }
+        let rank = std::iter::once(0)
+            .chain(words.iter().scan(0, |acc, w| {
+                *acc += w.count_ones();
+                Some(*acc)
+            }))
+            .collect();
+        Self { len: bits.len(), words, rank }
+    }
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.len);
+        self.words[i >> 6] >> (i & 63) & 1 == 1
+    }
+    /// Number of ones in `0..i`.
+    pub fn rank1(&self, i: usize) -> usize {
+        assert!(i <= self.len);
+        let mask = (1 << (i & 63)) - 1;
+        self.rank[i >> 6] as usize + (self.words[i >> 6] & mask).count_ones() as usize
+    }
+    /// Number of zeros in `0..i`.
+    pub fn rank0(&self, i: usize) -> usize {
+        i - self.rank1(i)
+    }
+    /// Position of the `k`-th (0-indexed) one.
+    pub fn select1(&self, k: usize) -> Option<usize> {
+        self._select(k, |i| self.rank[i] as usize, |w| w)
+    }
+    /// Position of the `k`-th (0-indexed) zero.
+    pub fn select0(&self, k: usize) -> Option<usize> {
+        self._select(k, |i| (i << 6) - self.rank[i] as usize, |w| !w)
+    }
+    fn _select<R: Fn(usize) -> usize, W: Fn(u64) -> u64>(&self, k: usize, rank: R, word: W) -> Option<usize> {
+        // the last word `i` with `rank(i) <= k`
+        let (mut i, mut hi) = (0, self.rank.len());
+        while hi - i > 1 {
+            let mid = (i + hi) / 2;
+            if rank(mid) <= k {
+                i = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        let mut w = word(self.words.get(i).copied()?);
+        (0..k - rank(i)).for_each(|_| w &= w.wrapping_sub(1));
+        let pos = (i << 6) + w.trailing_zeros() as usize;
+        (w != 0 && pos < self.len).then_some(pos)
+    }
+}
+
+pub struct WaveletMatrix {
+    len: usize,
+    height: u32,
+    levels: Box<[BitVector]>,
+    /// Number of zeros in each level.
+    zeros: Box<[usize]>,
+    /// Prefix sums of the values, in the order after each level. Empty unless built by [`Self::with_sums`].
+    sums: Box<[Box<[u64]>]>,
+}
+
+impl WaveletMatrix {
+    pub fn new(data: Vec<u64>) -> Self {
+        Self::_build(data, false)
+    }
+    /// Builds with per-level prefix sums as well, for [`Self::sum_smallest`].
+    pub fn with_sums(data: Vec<u64>) -> Self {
+        Self::_build(data, true)
+    }
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    pub fn get(&self, mut i: usize) -> u64 {
+        assert!(i < self.len);
+        let mut x = 0;
+        for (d, level) in self.levels.iter().enumerate() {
+            if level.get(i) {
+                x |= 1 << (self.height as usize - 1 - d);
+                i = self.zeros[d] + level.rank1(i);
+            } else {
+                i = level.rank0(i);
+            }
+        }
+        x
+    }
+    /// Number of occurrences of `x` in `0..i`.
+    pub fn rank(&self, x: u64, i: usize) -> usize {
+        assert!(i <= self.len);
+        if x.checked_shr(self.height).unwrap_or(0) != 0 {
+            return 0;
+        }
+        let (l, r) = self._descend(x, 0..i);
+        r - l
+    }
+    /// Position of the `k`-th (0-indexed) occurrence of `x`.
+    pub fn select(&self, x: u64, k: usize) -> Option<usize> {
+        if self.rank(x, self.len) <= k {
+            return None;
+        }
+        let (l, _) = self._descend(x, 0..0);
+        let mut pos = l + k;
+        for (d, level) in self.levels.iter().enumerate().rev() {
+            pos = if x >> (self.height as usize - 1 - d) & 1 == 1 {
+                level.select1(pos - self.zeros[d])?
+            } else {
+                level.select0(pos)?
+            };
+        }
+        Some(pos)
+    }
+    /// The `k`-th (0-indexed) smallest value in `range`.
+    pub fn kth_smallest(&self, range: std::ops::Range<usize>, mut k: usize) -> u64 {
+        assert!(range.start <= range.end && range.end <= self.len && k < range.len());
+        let (mut l, mut r) = (range.start, range.end);
+        let mut x = 0;
+        for (d, level) in self.levels.iter().enumerate() {
+            let (l0, r0) = (level.rank0(l), level.rank0(r));
+            if k < r0 - l0 {
+                (l, r) = (l0, r0);
+            } else {
+                k -= r0 - l0;
+                x |= 1 << (self.height as usize - 1 - d);
+                (l, r) = (self.zeros[d] + l - l0, self.zeros[d] + r - r0);
+            }
+        }
+        x
+    }
+    /// The `k`-th (0-indexed) largest value in `range`.
+    pub fn kth_largest(&self, range: std::ops::Range<usize>, k: usize) -> u64 {
+        let len = range.len();
+        self.kth_smallest(range, len - 1 - k)
+    }
+    /// Number of values less than `upper` in `range`.
+    pub fn count_less(&self, range: std::ops::Range<usize>, upper: u64) -> usize {
+        assert!(range.start <= range.end && range.end <= self.len);
+        if upper.checked_shr(self.height).unwrap_or(0) != 0 {
+            return range.len();
+        }
+        let (mut l, mut r) = (range.start, range.end);
+        let mut cnt = 0;
+        for (d, level) in self.levels.iter().enumerate() {
+            let (l0, r0) = (level.rank0(l), level.rank0(r));
+            if upper >> (self.height as usize - 1 - d) & 1 == 1 {
+                cnt += r0 - l0;
+                (l, r) = (self.zeros[d] + l - l0, self.zeros[d] + r - r0);
+            } else {
+                (l, r) = (l0, r0);
+            }
+        }
+        cnt
+    }
+    /// Number of values in `lower..upper` in `range`.
+    pub fn range_freq(&self, range: std::ops::Range<usize>, lower: u64, upper: u64) -> usize {
+        if lower >= upper {
+            return 0;
+        }
+        self.count_less(range.clone(), upper) - self.count_less(range, lower)
+    }
+    /// The largest value less than `upper` in `range`.
+    pub fn prev_value(&self, range: std::ops::Range<usize>, upper: u64) -> Option<u64> {
+        let cnt = self.count_less(range.clone(), upper);
+        (cnt != 0).then(|| self.kth_smallest(range, cnt - 1))
+    }
+    /// The smallest value not less than `lower` in `range`.
+    pub fn next_value(&self, range: std::ops::Range<usize>, lower: u64) -> Option<u64> {
+        let cnt = self.count_less(range.clone(), lower);
+        (cnt != range.len()).then(|| self.kth_smallest(range, cnt))
+    }
+    /// Sum of the `k` smallest values in `range`. Only available if built by [`Self::with_sums`].
+    pub fn sum_smallest(&self, range: std::ops::Range<usize>, mut k: usize) -> u64 {
+        assert!(!self.sums.is_empty(), "built without sums");
+        assert!(range.start <= range.end && range.end <= self.len && k <= range.len());
+        let (mut l, mut r) = (range.start, range.end);
+        let mut x = 0;
+        let mut sum = 0;
+        for (d, level) in self.levels.iter().enumerate() {
+            let (l0, r0) = (level.rank0(l), level.rank0(r));
+            if k < r0 - l0 {
+                (l, r) = (l0, r0);
+            } else {
+                k -= r0 - l0;
+                sum += self.sums[d][r0] - self.sums[d][l0];
+                x |= 1 << (self.height as usize - 1 - d);
+                (l, r) = (self.zeros[d] + l - l0, self.zeros[d] + r - r0);
+            }
+        }
+        sum + x * k as u64
+    }
+    /// Returns the range of the values equal to `x` after the last level, starting from `range`.
+    fn _descend(&self, x: u64, range: std::ops::Range<usize>) -> (usize, usize) {
+        let (mut l, mut r) = (range.start, range.end);
+        for (d, level) in self.levels.iter().enumerate() {
+            if x >> (self.height as usize - 1 - d) & 1 == 1 {
+                (l, r) = (self.zeros[d] + level.rank1(l), self.zeros[d] + level.rank1(r));
+            } else {
+                (l, r) = (level.rank0(l), level.rank0(r));
+            }
+        }
+        (l, r)
+    }
+    fn _build(mut data: Vec<u64>, with_sums: bool) -> Self {
+        let len = data.len();
+        let height = (u64::BITS - data.iter().max().map_or(0, |x| x.leading_zeros())).max(1);
+        let mut levels = Vec::with_capacity(height as usize);
+        let mut zeros = Vec::with_capacity(height as usize);
+        let mut sums = vec![];
+        for d in (0..height).rev() {
+            let bits = data.iter().map(|x| x >> d & 1 == 1).collect::<Vec<_>>();
+            let (mut lo, hi): (Vec<_>, Vec<_>) = data.iter().partition(|&&x| x >> d & 1 == 0);
+            zeros.push(lo.len());
+            lo.extend(hi);
+            data = lo;
+            levels.push(BitVector::new(&bits));
+            if with_sums {
+                let pref = std::iter::once(0).chain(data.iter().scan(0, |acc, &x| {
+                    *acc += x;
+                    Some(*acc)
+                }));
+                sums.push(pref.collect());
+            }
+        }
+        Self { len, height, levels: levels.into(), zeros: zeros.into(), sums: sums.into() }
+    }
+}
+
+#[test]
+fn bit_vector() {
+    let bits = (0..300).map(|i: usize| i.count_ones() % 3 == 1).collect::<Vec<_>>();
+    let bv = BitVector::new(&bits);
+    let ones = (0..bits.len()).filter(|&i| bits[i]).collect::<Vec<_>>();
+    let zeros = (0..bits.len()).filter(|&i| !bits[i]).collect::<Vec<_>>();
+    for i in 0..=bits.len() {
+        assert_eq!(bv.rank1(i), bits[..i].iter().filter(|&&b| b).count());
+    }
+    for k in 0..=bits.len() {
+        assert_eq!(bv.select1(k), ones.get(k).copied());
+        assert_eq!(bv.select0(k), zeros.get(k).copied());
+    }
+}
+
+#[test]
+fn wavelet_matrix() {
+    let a = vec![5_u64, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9, 0];
+    let wm = WaveletMatrix::with_sums(a.clone());
+    assert!((0..a.len()).all(|i| wm.get(i) == a[i]));
+    for l in 0..a.len() {
+        for r in l..=a.len() {
+            let mut sorted = a[l..r].to_vec();
+            sorted.sort();
+            for (k, &x) in sorted.iter().enumerate() {
+                assert_eq!(wm.kth_smallest(l..r, k), x);
+                assert_eq!(wm.kth_largest(l..r, r - l - 1 - k), x);
+            }
+            for k in 0..=sorted.len() {
+                assert_eq!(wm.sum_smallest(l..r, k), sorted[..k].iter().sum());
+            }
+            for x in 0..=11 {
+                assert_eq!(wm.count_less(l..r, x), sorted.iter().filter(|&&v| v < x).count());
+                assert_eq!(wm.range_freq(l..r, x, x + 3), sorted.iter().filter(|&&v| x <= v && v < x + 3).count());
+                assert_eq!(wm.prev_value(l..r, x), sorted.iter().rev().find(|&&v| v < x).copied());
+                assert_eq!(wm.next_value(l..r, x), sorted.iter().find(|&&v| v >= x).copied());
+            }
+        }
+    }
+    for x in 0..=11 {
+        let pos = (0..a.len()).filter(|&i| a[i] == x).collect::<Vec<_>>();
+        for i in 0..=a.len() {
+            assert_eq!(wm.rank(x, i), pos.iter().filter(|&&p| p < i).count());
+        }
+        for k in 0..=pos.len() {
+            assert_eq!(wm.select(x, k), pos.get(k).copied());
+        }
+    }
+}
+
+#[test]
+fn large_values() {
+    let a = [u64::MAX, 3, 1 << 63, 0, u64::MAX - 1, 1 << 63];
+    let wm = WaveletMatrix::new(a.to_vec());
+    assert_eq!(wm.count_less(0..3, 5), 1);
+    assert_eq!(wm.count_less(0..6, u64::MAX), 5);
+    assert_eq!(wm.range_freq(0..6, 1 << 63, u64::MAX), 3);
+    assert_eq!(wm.prev_value(0..6, u64::MAX), Some(u64::MAX - 1));
+    assert_eq!(wm.next_value(1..6, 4), Some(1 << 63));
+    assert_eq!(wm.rank(1 << 63, 6), 2);
+    assert_eq!(wm.select(u64::MAX, 0), Some(0));
+    assert_eq!(wm.kth_largest(0..6, 0), u64::MAX);
+}
@@ -7,13 +7,13 @@
 //! Gosper's Hack is an algorithm to find the next integer with the same number of set bits (population count).
 
 /// Iterate all combination of certain size.
-struct Gosper {
+pub struct Gosper {
     n: u32,
     mask: u32,
 }
 
 impl Gosper {
-    fn new(n: u32, k: u32) -> Self {
+    pub fn new(n: u32, k: u32) -> Self {
         Self { n, mask: (1 << k) - 1 }
     }
 }
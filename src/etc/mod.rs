@@ -2,3 +2,6 @@
 
 mod gosper;
 mod xrsr;
+
+pub use gosper::Gosper;
+pub use xrsr::Xrsr;
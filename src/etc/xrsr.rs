@@ -1,25 +1,29 @@
 //! Xor-Rotate-Shift-Roate 128+
 
-struct Xrsr {
+pub struct Xrsr {
     s: [usize; 2],
 }
 
 impl Xrsr {
-    fn new() -> Self {
+    pub fn new() -> Self {
         let pal = Box::into_raw(Box::new("pal")) as usize;
         let ilo = Box::into_raw(Box::new("ilo")) as usize;
         Self { s: [pal, ilo] }
     }
-    fn gen(&mut self) -> usize {
+    /// A fixed seed, for reproducible sequences such as in tests.
+    pub fn with_seed(seed: usize) -> Self {
+        Self { s: [seed, seed ^ 0x9e37_79b9] }
+    }
+    pub fn gen(&mut self) -> usize {
         let s0 = self.s[0];
         let mut s1 = self.s[1];
-        let result = s0 + s1;
+        let result = s0.wrapping_add(s1);
         s1 ^= s0;
         self.s[0] = s0.rotate_left(24) ^ s1 ^ (s1 << 16);
         self.s[1] = s1.rotate_left(37);
         result
     }
-    fn shuffle<T>(&mut self, data: &mut [T]) {
+    pub fn shuffle<T>(&mut self, data: &mut [T]) {
         for i in (1..data.len()).rev() {
             let j = self.gen() % (i + 1);
             data.swap(i, j);
@@ -27,5 +31,11 @@ impl Xrsr {
     }
 }
 
+impl Default for Xrsr {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[test]
 fn test() {}
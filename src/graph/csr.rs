@@ -1,6 +1,6 @@
 //! Compressed sparse row
 
-struct Csr<T> {
+pub struct Csr<T> {
     values: Box<[(usize, T)]>,
     pref: Box<[usize]>,
 }
@@ -13,7 +13,7 @@ impl<T> std::ops::Index<usize> for Csr<T> {
 }
 
 impl<T: Copy + Default> Csr<T> {
-    fn from_directed_edges(n: usize, edges: Vec<(usize, usize, T)>) -> Self {
+    pub fn from_directed_edges(n: usize, edges: Vec<(usize, usize, T)>) -> Self {
         let mut values = vec![(0, T::default()); edges.len()].into_boxed_slice();
         let mut pref = vec![0; n + 1].into_boxed_slice();
         for &(u, _, _) in edges.iter() {
@@ -28,7 +28,7 @@ impl<T: Copy + Default> Csr<T> {
         }
         Self { values, pref }
     }
-    fn from_undirected_edges(n: usize, edges: Vec<(usize, usize, T)>) -> Self {
+    pub fn from_undirected_edges(n: usize, edges: Vec<(usize, usize, T)>) -> Self {
         let mut values = vec![(0, T::default()); edges.len() * 2].into_boxed_slice();
         let mut pref = vec![0; n + 1].into_boxed_slice();
         for &(u, v, _) in edges.iter() {
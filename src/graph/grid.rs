@@ -1,4 +1,4 @@
-struct Grid<T> {
+pub struct Grid<T> {
     n: usize,
     m: usize,
     data: Box<[T]>,
@@ -18,16 +18,16 @@ impl<T> std::ops::IndexMut<(usize, usize)> for Grid<T> {
 }
 
 impl<T: Clone + Copy> Grid<T> {
-    fn new(n: usize, m: usize, default: T) -> Self {
+    pub fn new(n: usize, m: usize, default: T) -> Self {
         let data = vec![default; n * m].into_boxed_slice();
         Self { n, m, data }
     }
-    fn iter(&self) -> GridIter<'_, T> {
+    pub fn iter(&self) -> GridIter<'_, T> {
         GridIter { grid: self, x: 0, y: 0 }
     }
 }
 
-struct GridIter<'a, T> {
+pub struct GridIter<'a, T> {
     grid: &'a Grid<T>,
     x: usize,
     y: usize,
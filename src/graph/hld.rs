@@ -1,6 +1,6 @@
 //! Heavy-Light Decomposition
 
-struct Hld {
+pub struct Hld {
     par: Box<[usize]>,
     sub: Box<[usize]>,
     top: Box<[usize]>,
@@ -8,10 +8,13 @@ struct Hld {
 }
 
 impl Hld {
-    fn new(mut adj: Vec<Vec<usize>>) -> Self {
+    pub fn new(adj: Vec<Vec<usize>>) -> Self {
+        Self::with_root(adj, 0)
+    }
+    pub fn with_root(mut adj: Vec<Vec<usize>>, root: usize) -> Self {
         let n = adj.len();
         let mut topo = Vec::with_capacity(n);
-        topo.push(0);
+        topo.push(root);
         let mut par = vec![usize::MAX; n].into_boxed_slice();
         for i in 0..n {
             let u = topo[i];
@@ -23,7 +26,7 @@ impl Hld {
         }
         let mut sub = vec![1; n].into_boxed_slice();
         topo.iter().skip(1).rev().for_each(|&u| sub[par[u]] += sub[u]);
-        let mut top = vec![0; n].into_boxed_slice();
+        let mut top = vec![root; n].into_boxed_slice();
         for i in 0..n {
             let u = topo[i];
             if let Some(&heavy) = adj[u].iter().max_by_key(|&&v| sub[v]) {
@@ -40,7 +43,7 @@ impl Hld {
         topo.into_iter().enumerate().for_each(|(i, u)| tin[u] = i);
         Self { par, tin, top, sub }
     }
-    fn get_lca(&self, mut u: usize, mut v: usize) -> usize {
+    pub fn get_lca(&self, mut u: usize, mut v: usize) -> usize {
         while self.top[u] != self.top[v] {
             if self.sub[self.top[u]] < self.sub[self.top[v]] {
                 u = self.par[self.top[u]];
@@ -0,0 +1,122 @@
+//! Kruskal reconstruction tree
+//!
+//! Runs Kruskal's algorithm, and instead of uniting two components directly, creates a new node as their parent
+//! whose weight is the weight of the edge. Leaves `0..n` are the vertices, and nodes `n..` are the merges.
+//!
+//! - The bottleneck (minimum possible maximum edge) between `u` and `v` is the weight of their LCA.
+//! - The vertices reachable from `u` using edges of weight `<= w` are the leaves under the highest ancestor of `u`
+//!   with weight `<= w`.
+//!
+//! For a connected graph, it is a tree of `2n - 1` nodes rooted at `2n - 2`, which [`super::Hld::with_root`] takes.
+//!
+//! # Time Complexity
+//!
+//! O(m log m) for sorting the edges.
+
+use crate::data::DisjointSet;
+
+pub struct KruskalTree<T> {
+    n: usize,
+    par: Box<[usize]>,
+    children: Box<[[usize; 2]]>,
+    weight: Box<[T]>,
+}
+
+impl<T: Copy + Ord> KruskalTree<T> {
+    /// Edges are `(u, v, w)`.
+    pub fn new(n: usize, edges: &[(usize, usize, T)]) -> Self {
+        let mut edges = edges.to_vec();
+        edges.sort_by_key(|e| e.2);
+        let mut dsu = DisjointSet::new(n);
+        // the tree node of each component, indexed by its root in `dsu`
+        let mut node = (0..n).collect::<Vec<_>>();
+        let mut par = vec![usize::MAX; n];
+        let (mut children, mut weight) = (vec![], vec![]);
+        for (u, v, w) in edges {
+            let (u, v) = (dsu.find(u), dsu.find(v));
+            if dsu.unite(u, v) {
+                let id = par.len();
+                par[node[u]] = id;
+                par[node[v]] = id;
+                par.push(usize::MAX);
+                children.push([node[u], node[v]]);
+                weight.push(w);
+                node[dsu.find(u)] = id;
+            }
+        }
+        Self { n, par: par.into(), children: children.into(), weight: weight.into() }
+    }
+    /// Number of nodes.
+    pub fn len(&self) -> usize {
+        self.par.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.par.is_empty()
+    }
+    pub fn is_leaf(&self, u: usize) -> bool {
+        u < self.n
+    }
+    /// The weight of an internal node.
+    pub fn weight(&self, u: usize) -> T {
+        assert!(!self.is_leaf(u));
+        self.weight[u - self.n]
+    }
+    /// The parent of `u`, `None` for a root.
+    pub fn parent(&self, u: usize) -> Option<usize> {
+        Some(self.par[u]).filter(|&p| p != usize::MAX)
+    }
+    /// The two children of an internal node.
+    pub fn children(&self, u: usize) -> [usize; 2] {
+        assert!(!self.is_leaf(u));
+        self.children[u - self.n]
+    }
+    /// The roots, one for each connected component.
+    pub fn roots(&self) -> Vec<usize> {
+        (0..self.len()).filter(|&u| self.par[u] == usize::MAX).collect()
+    }
+    /// The undirected adjacency list of the tree (forest), e.g. for [`super::Hld::with_root`].
+    pub fn adj(&self) -> Vec<Vec<usize>> {
+        let mut adj = vec![vec![]; self.len()];
+        for (i, &[a, b]) in self.children.iter().enumerate() {
+            let u = self.n + i;
+            adj[u].extend([a, b]);
+            adj[a].push(u);
+            adj[b].push(u);
+        }
+        adj
+    }
+}
+
+#[test]
+fn test() {
+    use super::Hld;
+    let n = 9;
+    let mut rng = crate::etc::Xrsr::with_seed(0x2545_f491_4f6c_dd1d);
+    let mut edges = (1..n).map(|v| (rng.gen() % v, v, rng.gen() % 50)).collect::<Vec<_>>();
+    edges.extend((0..10).map(|_| (rng.gen() % n, rng.gen() % n, rng.gen() % 50)));
+    let tree = KruskalTree::new(n, &edges);
+    assert_eq!(tree.len(), 2 * n - 1);
+    assert_eq!(tree.roots(), [2 * n - 2]);
+    assert_eq!(tree.parent(2 * n - 2), None);
+    for u in n..2 * n - 1 {
+        for c in tree.children(u) {
+            assert_eq!(tree.parent(c), Some(u));
+            assert!(tree.is_leaf(c) || tree.weight(c) <= tree.weight(u));
+        }
+    }
+    let hld = Hld::with_root(tree.adj(), 2 * n - 2);
+    for u in 0..n {
+        for v in 0..n {
+            // the smallest `w` such that `u` and `v` are connected using edges `<= w`
+            let naive = (0..50).find(|&w| {
+                let mut dsu = DisjointSet::new(n);
+                edges.iter().filter(|e| e.2 <= w).for_each(|e| {
+                    dsu.unite(e.0, e.1);
+                });
+                dsu.same(u, v)
+            });
+            let lca = hld.get_lca(u, v);
+            assert_eq!(if u == v { Some(0) } else { Some(tree.weight(lca)) }, naive);
+        }
+    }
+}
@@ -1,20 +1,20 @@
 //! Bipartite Matching (Kuhn's Algorithm)
 
-struct BipartiteMatching {
+pub struct BipartiteMatching {
     n: usize,
     m: usize,
     adj: Vec<Vec<usize>>,
 }
 
 impl BipartiteMatching {
-    fn new(n: usize, m: usize) -> Self {
+    pub fn new(n: usize, m: usize) -> Self {
         Self { n, m, adj: vec![vec![]; n] }
     }
-    fn add_edge(&mut self, u: usize, v: usize) {
+    pub fn add_edge(&mut self, u: usize, v: usize) {
         assert!(u < self.n && v < self.m);
         self.adj[u].push(v);
     }
-    fn maximum_matching(&self) -> Vec<usize> {
+    pub fn maximum_matching(&self) -> Vec<usize> {
         let mut check = vec![false; self.n];
         let mut wife = vec![usize::MAX; self.n];
         let mut hubby = vec![usize::MAX; self.m];
@@ -3,4 +3,11 @@
 mod csr;
 mod grid;
 mod hld;
+mod kruskal;
 mod matching;
+
+pub use csr::Csr;
+pub use grid::{Grid, GridIter};
+pub use hld::Hld;
+pub use kruskal::KruskalTree;
+pub use matching::BipartiteMatching;
@@ -0,0 +1,7 @@
+//! Input and output
+
+mod scanner;
+mod writer;
+
+pub use scanner::{Scan, Scanner};
+pub use writer::Writer;
@@ -0,0 +1,201 @@
+//! Whole-input byte scanner
+//!
+//! Reads the entire input at once and splits it into whitespace-separated tokens.
+//! Every type implementing [`Scan`] can be read with [`Scanner::next`].
+
+pub struct Scanner {
+    buf: Box<[u8]>,
+    pos: usize,
+}
+
+pub trait Scan: Sized {
+    fn scan(sc: &mut Scanner) -> Self;
+}
+
+impl Scanner {
+    pub fn new<R: std::io::Read>(mut reader: R) -> Self {
+        let mut buf = vec![];
+        reader.read_to_end(&mut buf).expect("failed to read input");
+        Self { buf: buf.into(), pos: 0 }
+    }
+    pub fn stdin() -> Self {
+        Self::new(std::io::stdin().lock())
+    }
+    /// Returns the next token, or an empty slice at the end of the input.
+    pub fn token(&mut self) -> &[u8] {
+        while self.pos < self.buf.len() && self.buf[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+        let start = self.pos;
+        while self.pos < self.buf.len() && !self.buf[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+        &self.buf[start..self.pos]
+    }
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<T: Scan>(&mut self) -> T {
+        T::scan(self)
+    }
+    /// Parses the next token with [`std::str::FromStr`].
+    pub fn parse<T>(&mut self) -> T
+    where
+        T: std::str::FromStr,
+        T::Err: std::fmt::Debug,
+    {
+        std::str::from_utf8(self.token()).unwrap().parse().unwrap()
+    }
+    pub fn vec<T: Scan>(&mut self, n: usize) -> Vec<T> {
+        (0..n).map(|_| self.next()).collect()
+    }
+    /// Reads `m` edges `u v [w]`, subtracting `base` from the endpoints.
+    ///
+    /// The result can be passed to [`crate::graph::Csr`] as is. Use `T = ()` for unweighted edges.
+    pub fn edges<T: Scan>(&mut self, m: usize, base: usize) -> Vec<(usize, usize, T)> {
+        (0..m).map(|_| (self.next::<usize>() - base, self.next::<usize>() - base, self.next())).collect()
+    }
+}
+
+/// Panics if the input ended before `token`.
+fn nonempty(token: &[u8]) -> &[u8] {
+    assert!(!token.is_empty(), "unexpected EOF");
+    token
+}
+
+/// Panics unless `token` is a non-empty run of ASCII digits, just like [`str::parse`] would.
+fn digits(token: &[u8]) -> &[u8] {
+    let valid = !token.is_empty() && token.iter().all(u8::is_ascii_digit);
+    assert!(valid, "invalid integer: {:?}", String::from_utf8_lossy(token));
+    token
+}
+
+macro_rules! impl_scan_unsigned {
+    ($($t: ty),*) => {$(
+        impl Scan for $t {
+            fn scan(sc: &mut Scanner) -> Self {
+                match nonempty(sc.token()) {
+                    [b'+', token @ ..] | token => digits(token).iter().fold(0, |acc, &c| acc * 10 + (c - b'0') as $t),
+                }
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_scan_signed {
+    ($($t: ty),*) => {$(
+        impl Scan for $t {
+            fn scan(sc: &mut Scanner) -> Self {
+                match nonempty(sc.token()) {
+                    [b'-', token @ ..] => digits(token).iter().fold(0, |acc, &c| acc * 10 - (c - b'0') as $t),
+                    [b'+', token @ ..] | token => digits(token).iter().fold(0, |acc, &c| acc * 10 + (c - b'0') as $t),
+                }
+            }
+        }
+    )*};
+}
+
+impl_scan_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_scan_signed!(i8, i16, i32, i64, i128, isize);
+
+impl Scan for f64 {
+    fn scan(sc: &mut Scanner) -> Self {
+        sc.parse()
+    }
+}
+
+impl Scan for char {
+    fn scan(sc: &mut Scanner) -> Self {
+        nonempty(sc.token())[0] as char
+    }
+}
+
+impl<const M: u32> Scan for crate::math::ModNum<{ M }> {
+    fn scan(sc: &mut Scanner) -> Self {
+        sc.parse()
+    }
+}
+
+impl Scan for Vec<u8> {
+    fn scan(sc: &mut Scanner) -> Self {
+        sc.token().to_vec()
+    }
+}
+
+impl Scan for String {
+    fn scan(sc: &mut Scanner) -> Self {
+        String::from_utf8(sc.token().to_vec()).unwrap()
+    }
+}
+
+impl Scan for () {
+    fn scan(_: &mut Scanner) -> Self {}
+}
+
+impl<T: Scan, const N: usize> Scan for [T; N] {
+    fn scan(sc: &mut Scanner) -> Self {
+        std::array::from_fn(|_| sc.next())
+    }
+}
+
+macro_rules! impl_scan_tuple {
+    ($($t: ident),*) => {
+        impl<$($t: Scan),*> Scan for ($($t,)*) {
+            fn scan(sc: &mut Scanner) -> Self {
+                ($(sc.next::<$t>(),)*)
+            }
+        }
+    };
+}
+
+impl_scan_tuple!(A, B);
+impl_scan_tuple!(A, B, C);
+impl_scan_tuple!(A, B, C, D);
+
+#[test]
+fn scan_primitives() {
+    let mut sc = Scanner::new(&b"  42 -7 +3 +0\n18446744073709551615 -9223372036854775808 x 2.5\r\nhello"[..]);
+    assert_eq!(sc.next::<usize>(), 42);
+    assert_eq!(sc.next::<i32>(), -7);
+    assert_eq!(sc.next::<i32>(), 3);
+    assert_eq!(sc.next::<u8>(), 0);
+    assert_eq!(sc.next::<u64>(), u64::MAX);
+    assert_eq!(sc.next::<i64>(), i64::MIN);
+    assert_eq!(sc.next::<char>(), 'x');
+    assert_eq!(sc.next::<f64>(), 2.5);
+    assert_eq!(sc.next::<Vec<u8>>(), b"hello");
+    assert!(sc.token().is_empty());
+}
+
+#[test]
+fn scan_compound() {
+    let mut sc = Scanner::new(&b"3\n1 2 3\n1 2 -5\n2 3 7\n3 1\n1 2"[..]);
+    let n = sc.next::<usize>();
+    assert_eq!(sc.next::<[i32; 3]>(), [1, 2, 3]);
+    assert_eq!(sc.edges::<i64>(n - 1, 1), [(0, 1, -5), (1, 2, 7)]);
+    assert_eq!(sc.edges::<()>(2, 1), [(2, 0, ()), (0, 1, ())]);
+}
+
+#[test]
+#[should_panic(expected = "unexpected EOF")]
+fn scan_past_eof() {
+    let mut sc = Scanner::new(&b"1\n"[..]);
+    sc.next::<u32>();
+    sc.next::<u32>();
+}
+
+#[test]
+#[should_panic]
+fn scan_non_digits() {
+    Scanner::new(&b"-abc"[..]).next::<i64>();
+}
+
+#[test]
+#[should_panic(expected = "unexpected EOF")]
+fn scan_char_past_eof() {
+    Scanner::new(&b" \n"[..]).next::<char>();
+}
+
+#[test]
+#[should_panic(expected = "invalid integer")]
+fn scan_sign_only() {
+    Scanner::new(&b"+"[..]).next::<u32>();
+}
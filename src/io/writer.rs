@@ -0,0 +1,69 @@
+//! Buffered output writer
+
+use std::io::Write;
+
+pub struct Writer<W: std::io::Write> {
+    inner: std::io::BufWriter<W>,
+}
+
+impl Writer<std::io::StdoutLock<'static>> {
+    pub fn stdout() -> Self {
+        Self::new(std::io::stdout().lock())
+    }
+}
+
+impl<W: std::io::Write> Writer<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner: std::io::BufWriter::with_capacity(1 << 16, inner) }
+    }
+    pub fn put<T: std::fmt::Display>(&mut self, x: T) -> &mut Self {
+        write!(self.inner, "{x}").unwrap();
+        self
+    }
+    pub fn putln<T: std::fmt::Display>(&mut self, x: T) -> &mut Self {
+        writeln!(self.inner, "{x}").unwrap();
+        self
+    }
+    /// Writes the items separated by `sep`, without a trailing newline.
+    pub fn join<I>(&mut self, iter: I, sep: &str) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: std::fmt::Display,
+    {
+        let mut iter = iter.into_iter();
+        if let Some(x) = iter.next() {
+            write!(self.inner, "{x}").unwrap();
+            iter.for_each(|x| write!(self.inner, "{sep}{x}").unwrap());
+        }
+        self
+    }
+    pub fn joinln<I>(&mut self, iter: I, sep: &str) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: std::fmt::Display,
+    {
+        self.join(iter, sep).put('\n')
+    }
+    pub fn into_inner(self) -> W {
+        self.inner.into_inner().ok().unwrap()
+    }
+}
+
+impl<W: std::io::Write> Write for Writer<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner.write(buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[test]
+fn join() {
+    let mut out = Writer::new(vec![]);
+    out.put(1).put(' ').putln("two");
+    out.joinln([3, 4, 5], " ").joinln(Vec::<i32>::new(), " ");
+    out.join(["a", "b"].iter(), ", ");
+    writeln!(out, "!").unwrap();
+    assert_eq!(out.into_inner(), b"1 two\n3 4 5\n\na, b!\n");
+}
@@ -1,6 +1,23 @@
-#![allow(dead_code)]
-mod data;
-mod etc;
-mod graph;
-mod math;
-mod string;
+//! My Rust algorithm library, for use in programming competitions.
+//!
+//! Everything commonly needed in a solution is re-exported from [`prelude`].
+
+pub mod data;
+pub mod etc;
+pub mod graph;
+pub mod io;
+pub mod math;
+pub mod string;
+
+/// Re-exports of the most frequently used items.
+pub mod prelude {
+    pub use crate::data::{
+        Additive, BinaryIndexedTree, CommutativeMonoid, DisjointSet, Group, Idempotent, LazySeg, MapMonoid, Max, Min,
+        Monoid, SegmentTree, SparseTable,
+    };
+    pub use crate::etc::{Gosper, Xrsr};
+    pub use crate::graph::{BipartiteMatching, Csr, Grid, Hld};
+    pub use crate::io::{Scan, Scanner, Writer};
+    pub use crate::math::{eratosthenes, Gcd, Matrix, ModNum};
+    pub use crate::string::{ZeroHasher, H};
+}
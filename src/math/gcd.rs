@@ -1,4 +1,4 @@
-trait Gcd {
+pub trait Gcd {
     fn gcd(x: Self, y: Self) -> Self;
     fn lcm(x: Self, y: Self) -> Self;
 }
@@ -34,3 +34,45 @@ macro_rules! impl_gcd {
         }
     };
 }
+
+impl_gcd!(u8);
+impl_gcd!(u16);
+impl_gcd!(u32);
+impl_gcd!(u64);
+impl_gcd!(u128);
+impl_gcd!(usize);
+
+/// Signed integers go through their absolute values, so the results are never negative,
+/// except when the result is `2^(bits - 1)` (e.g. `gcd(MIN, 0)`), which wraps around to `MIN`.
+macro_rules! impl_gcd_signed {
+    ($t: ty, $u: ty) => {
+        impl Gcd for $t {
+            fn gcd(u: Self, v: Self) -> Self {
+                <$u>::gcd(u.unsigned_abs(), v.unsigned_abs()) as Self
+            }
+            fn lcm(u: Self, v: Self) -> Self {
+                <$u>::lcm(u.unsigned_abs(), v.unsigned_abs()) as Self
+            }
+        }
+    };
+}
+
+impl_gcd_signed!(i8, u8);
+impl_gcd_signed!(i16, u16);
+impl_gcd_signed!(i32, u32);
+impl_gcd_signed!(i64, u64);
+impl_gcd_signed!(i128, u128);
+impl_gcd_signed!(isize, usize);
+
+#[test]
+fn gcd_lcm() {
+    assert_eq!(u32::gcd(0, 0), 0);
+    assert_eq!(u32::gcd(0, 12), 12);
+    assert_eq!(u64::gcd(12, 18), 6);
+    assert_eq!(u64::lcm(12, 18), 36);
+    assert_eq!(usize::gcd(1 << 20, 3 << 10), 1 << 10);
+    assert_eq!(i32::gcd(-12, 18), 6);
+    assert_eq!(i64::gcd(-12, -18), 6);
+    assert_eq!(i64::gcd(0, -7), 7);
+    assert_eq!(i64::lcm(-4, 6), 12);
+}
@@ -1,6 +1,6 @@
 #[derive(Clone, Copy, Debug)]
-struct Matrix<const N: usize, const M: usize, T> {
-    data: [[T; M]; N],
+pub struct Matrix<const N: usize, const M: usize, T> {
+    pub data: [[T; M]; N],
 }
 
 impl<const N: usize, const M: usize, T> std::ops::Add<Self> for Matrix<N, M, T>
@@ -40,7 +40,7 @@ where
 }
 
 impl<const N: usize, const M: usize, T> Matrix<N, M, T> {
-    fn new(data: [[T; M]; N]) -> Self {
+    pub fn new(data: [[T; M]; N]) -> Self {
         Self { data }
     }
 }
@@ -4,3 +4,8 @@ mod gcd;
 mod matrix;
 mod modnum;
 mod sieve;
+
+pub use gcd::Gcd;
+pub use matrix::Matrix;
+pub use modnum::ModNum;
+pub use sieve::eratosthenes;
@@ -1,14 +1,17 @@
-#[derive(Clone, Copy, PartialEq, Eq)]
-struct ModNum<const M: u32>(u32);
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct ModNum<const M: u32>(u32);
 
 impl<const M: u32> ModNum<{ M }> {
-    const fn new(v: u32) -> Self {
+    pub const fn new(v: u32) -> Self {
         Self(v % M)
     }
-    const fn new_unchecked(v: u32) -> Self {
+    pub const fn new_unchecked(v: u32) -> Self {
         Self(v)
     }
-    const fn pow(&self, mut n: u64) -> Self {
+    pub const fn val(&self) -> u32 {
+        self.0
+    }
+    pub const fn pow(&self, mut n: u64) -> Self {
         let mut r = 1;
         let mut a = self.0 as u64;
         while n != 0 {
@@ -20,11 +23,11 @@ impl<const M: u32> ModNum<{ M }> {
         }
         Self(r as u32)
     }
-    const fn inv(&self) -> Self {
+    pub const fn inv(&self) -> Self {
         assert!(self.0 != 0);
         self.pow(M as u64 - 2)
     }
-    const fn neg(&self) -> Self {
+    pub const fn neg(&self) -> Self {
         Self(if self.0 == 0 { 0 } else { M - self.0 })
     }
 }
@@ -40,8 +43,8 @@ impl<const M: u32> std::ops::Add for ModNum<{ M }> {
 impl<const M: u32> std::ops::Sub for ModNum<{ M }> {
     type Output = Self;
     fn sub(self, rhs: Self) -> Self {
-        let r = self.0 - rhs.0 + M;
-        Self(if r < M { r } else { r - M })
+        let (r, borrow) = self.0.overflowing_sub(rhs.0);
+        Self(if borrow { r.wrapping_add(M) } else { r })
     }
 }
 
@@ -54,6 +57,7 @@ impl<const M: u32> std::ops::Mul for ModNum<{ M }> {
 
 impl<const M: u32> std::ops::Div for ModNum<{ M }> {
     type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn div(self, rhs: Self) -> Self {
         self * rhs.inv()
     }
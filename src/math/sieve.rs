@@ -1,4 +1,4 @@
-fn eratosthenes(n: usize) -> Vec<bool> {
+pub fn eratosthenes(n: usize) -> Vec<bool> {
     let mut sieve = vec![false; n];
     sieve[0] = true;
     sieve[1] = true;
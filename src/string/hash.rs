@@ -1,7 +1,7 @@
 //! mod 2^64 - 1 hashing
 
 #[derive(Clone, Copy, Default)]
-struct ZeroHasher(u64);
+pub struct ZeroHasher(u64);
 
 impl std::hash::Hasher for ZeroHasher {
     fn write_u64(&mut self, i: u64) {
@@ -23,13 +23,13 @@ impl std::hash::BuildHasher for ZeroHasher {
 }
 
 #[derive(Clone, Copy, Debug, Default)]
-struct H(u64);
+pub struct H(u64);
 
 impl H {
-    const fn new(x: u64) -> Self {
+    pub const fn new(x: u64) -> Self {
         Self(x)
     }
-    const fn get(&self) -> u64 {
+    pub const fn get(&self) -> u64 {
         if self.0 == u64::MAX {
             0
         } else {
@@ -131,7 +131,7 @@ fn arithmetic_operators() {
 #[test]
 fn hash() {
     let mut set = std::collections::HashSet::new();
-    assert_eq!(set.insert(H(0)), true);
-    assert_eq!(set.insert(H(u64::MAX)), false);
-    assert_eq!(set.insert(H(1)), true);
+    assert!(set.insert(H(0)));
+    assert!(!set.insert(H(u64::MAX)));
+    assert!(set.insert(H(1)));
 }
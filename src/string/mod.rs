@@ -1,3 +1,5 @@
 //! String algorithms
 
 mod hash;
+
+pub use hash::{ZeroHasher, H};
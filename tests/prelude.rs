@@ -0,0 +1,44 @@
+use pral::prelude::*;
+
+#[test]
+fn segment_tree() {
+    let mut seg = SegmentTree::new(5, 0, std::ops::Add::add);
+    (0..5).for_each(|i| seg.set(i, i as i64 + 1));
+    assert_eq!(seg.prod(1..4), 9);
+}
+
+#[test]
+fn disjoint_set() {
+    let mut dsu = DisjointSet::new(4);
+    dsu.unite(0, 3);
+    assert!(dsu.same(3, 0));
+    assert_eq!(dsu.num_components(), 3);
+}
+
+#[test]
+fn modnum() {
+    type M = ModNum<998_244_353>;
+    let x: M = "998244354".parse().unwrap();
+    assert_eq!(x, M::new(1));
+    assert_eq!((M::new(3) / M::new(3)).val(), 1);
+}
+
+#[test]
+fn modnum_sub() {
+    type M = ModNum<998_244_353>;
+    assert_eq!(M::new(1) - M::new(3), M::new(998_244_351));
+    assert_eq!(M::new(0) - M::new(998_244_352), M::new(1));
+    let mut x = M::new(5);
+    x -= M::new(7);
+    assert_eq!(x, M::new(998_244_351));
+}
+
+#[test]
+fn scan_into_csr() {
+    type M = ModNum<998_244_353>;
+    let mut sc = Scanner::new(&b"3 2\n1 2 5\n2 3 7\n998244355"[..]);
+    let (n, m) = sc.next::<(usize, usize)>();
+    let csr = Csr::from_undirected_edges(n, sc.edges::<i64>(m, 1));
+    assert_eq!(csr[1], [(2, 7), (0, 5)]);
+    assert_eq!(sc.next::<M>(), M::new(2));
+}